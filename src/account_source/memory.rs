@@ -0,0 +1,184 @@
+use {
+    crate::{account_source::AccountSource, constants::*, error::*, utils::RpcTokenAccounts},
+    async_trait::async_trait,
+    solana_account_decoder::UiAccount,
+    solana_client::rpc_filter::RpcFilterType,
+    solana_sdk::{
+        account::{Account, AccountSharedData},
+        pubkey::Pubkey,
+    },
+    spl_token_2022::{
+        extension::StateWithExtensions,
+        state::{Account as TokenAccount, Mint},
+    },
+    std::{
+        collections::{BTreeMap, HashMap},
+        fs,
+        io::{self, ErrorKind},
+        path::Path,
+        str::FromStr,
+    },
+};
+
+/**
+ * In-memory account store, useful for tests and offline parsing.
+ */
+#[derive(Clone, Debug, Default)]
+pub struct MemoryAccountSource {
+    accounts: HashMap<Pubkey, Account>,
+}
+
+impl MemoryAccountSource {
+    /// maximum number of accounts returned by `get_token_largest_accounts`, same as the rpc.
+    pub const MAX_TOKEN_LARGEST_ACCOUNTS: usize = 20;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds or replaces an account.
+    pub fn insert(&mut self, pubkey: Pubkey, account: Account) -> Option<Account> {
+        self.accounts.insert(pubkey, account)
+    }
+
+    /// Removes an account.
+    pub fn remove(&mut self, pubkey: &Pubkey) -> Option<Account> {
+        self.accounts.remove(pubkey)
+    }
+
+    pub fn get(&self, pubkey: &Pubkey) -> Option<&Account> {
+        self.accounts.get(pubkey)
+    }
+
+    pub fn len(&self) -> usize {
+        self.accounts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+
+    /// Adds the accounts of a fixture: a json object mapping each pubkey to its
+    /// base64 `getAccountInfo` response, either pasted whole or only its `result` or `value`.
+    /// A `null` value, i.e. an account that does not exist, is skipped.
+    pub fn load_fixture_json(&mut self, json: &str) -> TldParserResult<()> {
+        let fixture: BTreeMap<String, serde_json::Value> =
+            serde_json::from_str(json).map_err(io::Error::from)?;
+        for (pubkey, response) in fixture {
+            let pubkey = Pubkey::from_str(&pubkey)
+                .map_err(|_| invalid_fixture(format!("{} is not a valid pubkey", pubkey)))?;
+            let result = response.get("result").unwrap_or(&response);
+            let value = result.get("value").unwrap_or(result);
+            if value.is_null() {
+                continue;
+            }
+            let ui_account: UiAccount =
+                serde_json::from_value(value.clone()).map_err(io::Error::from)?;
+            let account = ui_account.decode::<Account>().ok_or_else(|| {
+                invalid_fixture(format!("account {} data could not be decoded", pubkey))
+            })?;
+            self.insert(pubkey, account);
+        }
+        Ok(())
+    }
+
+    /// Adds the accounts of a fixture file, see `load_fixture_json`.
+    pub fn load_fixture_file(&mut self, path: impl AsRef<Path>) -> TldParserResult<()> {
+        self.load_fixture_json(&fs::read_to_string(path)?)
+    }
+
+    /// Adds the accounts of every `.json` fixture file in a directory.
+    pub fn load_fixture_dir(&mut self, dir: impl AsRef<Path>) -> TldParserResult<()> {
+        let mut paths = fs::read_dir(dir)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<io::Result<Vec<_>>>()?;
+        paths.sort();
+        for path in paths
+            .iter()
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        {
+            self.load_fixture_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+fn invalid_fixture(message: String) -> TldParserError {
+    TldParserError::Deserialization(io::Error::new(ErrorKind::InvalidData, message))
+}
+
+impl FromIterator<(Pubkey, Account)> for MemoryAccountSource {
+    fn from_iter<I: IntoIterator<Item = (Pubkey, Account)>>(iter: I) -> Self {
+        Self {
+            accounts: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl AccountSource for MemoryAccountSource {
+    async fn get_account(&self, pubkey: &Pubkey) -> TldParserResult<Option<Account>> {
+        Ok(self.accounts.get(pubkey).cloned())
+    }
+
+    async fn get_multiple_accounts(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> TldParserResult<Vec<Option<Account>>> {
+        Ok(pubkeys
+            .iter()
+            .map(|pubkey| self.accounts.get(pubkey).cloned())
+            .collect())
+    }
+
+    async fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        filters: Vec<RpcFilterType>,
+    ) -> TldParserResult<Vec<(Pubkey, Account)>> {
+        Ok(self
+            .accounts
+            .iter()
+            .filter(|(_, account)| account.owner == *program_id)
+            .filter(|(_, account)| {
+                let shared_account = AccountSharedData::from((*account).clone());
+                filters.iter().all(|filter| filter.allows(&shared_account))
+            })
+            .map(|(pubkey, account)| (*pubkey, account.clone()))
+            .collect())
+    }
+
+    async fn get_token_largest_accounts(
+        &self,
+        mint: &Pubkey,
+    ) -> TldParserResult<Vec<RpcTokenAccounts>> {
+        let decimals = self
+            .accounts
+            .get(mint)
+            .and_then(|account| StateWithExtensions::<Mint>::unpack(&account.data).ok())
+            .map(|mint| mint.base.decimals)
+            .unwrap_or_default();
+        let mut token_accounts: Vec<(Pubkey, u64)> = self
+            .accounts
+            .iter()
+            .filter(|(_, account)| {
+                account.owner == spl_token_2022::id() || account.owner == TOKEN_PROGRAM_ID
+            })
+            .filter_map(|(pubkey, account)| {
+                let token_account =
+                    StateWithExtensions::<TokenAccount>::unpack(&account.data).ok()?;
+                (token_account.base.mint == *mint).then_some((*pubkey, token_account.base.amount))
+            })
+            .collect();
+        token_accounts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        Ok(token_accounts
+            .into_iter()
+            .take(Self::MAX_TOKEN_LARGEST_ACCOUNTS)
+            .map(|(address, amount)| RpcTokenAccounts {
+                address: address.to_string(),
+                amount: amount.to_string(),
+                decimals,
+            })
+            .collect())
+    }
+}
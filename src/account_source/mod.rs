@@ -0,0 +1,49 @@
+mod memory;
+mod rpc;
+pub use memory::*;
+
+use {
+    crate::{error::*, utils::RpcTokenAccounts},
+    async_trait::async_trait,
+    solana_client::rpc_filter::RpcFilterType,
+    solana_sdk::{account::Account, pubkey::Pubkey},
+};
+
+/**
+ * Source of on-chain accounts used by the Tld Parser.
+ *
+ * Implemented for the nonblocking `RpcClient` and for the in-memory
+ * `MemoryAccountSource`, so the parser can run without a live rpc.
+ */
+#[async_trait]
+pub trait AccountSource: Send + Sync {
+    /// Returns the account, or `None` if it does not exist.
+    async fn get_account(&self, pubkey: &Pubkey) -> TldParserResult<Option<Account>>;
+
+    /// Returns the accounts in the same order as `pubkeys`.
+    async fn get_multiple_accounts(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> TldParserResult<Vec<Option<Account>>>;
+
+    /// Returns every account owned by `program_id` that matches all `filters`.
+    async fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        filters: Vec<RpcFilterType>,
+    ) -> TldParserResult<Vec<(Pubkey, Account)>>;
+
+    /// Returns the largest token accounts of `mint`, largest first.
+    async fn get_token_largest_accounts(
+        &self,
+        mint: &Pubkey,
+    ) -> TldParserResult<Vec<RpcTokenAccounts>>;
+
+    /// Returns the data of an account, or `AccountNotFound` if it does not exist.
+    async fn get_account_data(&self, pubkey: &Pubkey) -> TldParserResult<Vec<u8>> {
+        self.get_account(pubkey)
+            .await?
+            .map(|account| account.data)
+            .ok_or(TldParserError::AccountNotFound(*pubkey))
+    }
+}
@@ -0,0 +1,56 @@
+use {
+    crate::{account_source::AccountSource, error::*, utils::*},
+    async_trait::async_trait,
+    solana_account_decoder::UiAccountEncoding,
+    solana_client::{
+        nonblocking::rpc_client::RpcClient,
+        rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+        rpc_filter::RpcFilterType,
+    },
+    solana_sdk::{account::Account, pubkey::Pubkey},
+};
+
+#[async_trait]
+impl AccountSource for RpcClient {
+    async fn get_account(&self, pubkey: &Pubkey) -> TldParserResult<Option<Account>> {
+        Ok(self
+            .get_account_with_commitment(pubkey, self.commitment())
+            .await?
+            .value)
+    }
+
+    async fn get_multiple_accounts(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> TldParserResult<Vec<Option<Account>>> {
+        Ok(RpcClient::get_multiple_accounts(self, pubkeys).await?)
+    }
+
+    async fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        filters: Vec<RpcFilterType>,
+    ) -> TldParserResult<Vec<(Pubkey, Account)>> {
+        let rpc_config = RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            data_slice: None,
+            commitment: None,
+            min_context_slot: None,
+        };
+        let config = RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: rpc_config,
+            with_context: None,
+        };
+        Ok(self
+            .get_program_accounts_with_config(program_id, config)
+            .await?)
+    }
+
+    async fn get_token_largest_accounts(
+        &self,
+        mint: &Pubkey,
+    ) -> TldParserResult<Vec<RpcTokenAccounts>> {
+        Ok(get_token_largest_accounts(self, mint).await?.value)
+    }
+}
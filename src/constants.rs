@@ -9,7 +9,24 @@ pub const NAME_HOUSE_PREFIX: &str = "name_house";
 pub const COLLECTION_PREFIX: &str = "name_collection";
 pub const NFT_RECORD_PREFIX: &str = "nft_record";
 
+/// prefix of the hashed label of a subdomain e.g. "0sub" for "sub.miester.abc"
+pub const SUBDOMAIN_PREFIX: &str = "0";
+/// prefix of the hashed label of a record e.g. "1url" for "url.miester.abc"
+pub const RECORD_PREFIX: &str = "1";
+/// prefix of the hashed label of the subdomain holding four level records
+/// e.g. "\0sub" for "url.sub.miester.abc"
+pub const SUBDOMAIN_RECORD_PARENT_PREFIX: &str = "\0";
+/// maximum number of name levels below a tld, as in "record.subdomain.domain.tld"
+pub const MAX_NAME_DEPTH: usize = 3;
+
+/// maximum number of accounts per `getMultipleAccounts` request.
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// time in seconds an expired domain can still be renewed by its owner, 45 days.
+pub const DEFAULT_GRACE_PERIOD: u64 = 45 * 24 * 60 * 60;
+
 pub const ANS_PROGRAM_ID: Pubkey = pubkey!("ALTNSZ46uaAUU7XUV6awvdorLGqAsPwa9shm7h4uP2FK");
 pub const TLD_HOUSE_PROGRAM_ID: Pubkey = pubkey!("TLDHkysf5pCnKsVA4gXpNvmy7psXLPEu4LAdDJthT9S");
 pub const ORIGIN_TLD_KEY: Pubkey = pubkey!("3mX9b4AZaQehNoQGfckVcmgmA6bkBoFcbLj9RMmMyNcU");
 pub const NAME_HOUSE_PROGRAM_ID: Pubkey = pubkey!("NH3uX6FtVE2fNREAioP7hm5RaozotZxeL6khU1EHx51");
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
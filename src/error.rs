@@ -0,0 +1,63 @@
+use {
+    crate::types::Record,
+    solana_client::client_error::ClientError,
+    solana_sdk::{program_error::ProgramError, pubkey::Pubkey},
+    thiserror::Error,
+};
+
+/**
+ * Errors returned by the Tld Parser.
+ */
+#[derive(Debug, Error)]
+pub enum TldParserError {
+    /// the account does not exist on chain.
+    #[error("account {0} not found")]
+    AccountNotFound(Pubkey),
+    /// the domain could not be parsed or derived.
+    #[error("invalid domain: {0}")]
+    InvalidDomain(String),
+    /// the account data could not be deserialized.
+    #[error("failed to deserialize account data")]
+    Deserialization(#[from] std::io::Error),
+    /// the account data is shorter than its layout requires.
+    #[error("account data too short: expected at least {expected} bytes, found {actual}")]
+    AccountDataTooShort { expected: usize, actual: usize },
+    /// the account data does not start with the discriminator of the expected anchor account.
+    #[error("account data is not a {0} account")]
+    InvalidDiscriminator(&'static str),
+    /// a string stored in the account data is not valid utf-8.
+    #[error("account data contains invalid utf-8")]
+    InvalidUtf8(#[from] std::str::Utf8Error),
+    /// the payload of a record is not a valid value for that record e.g. a malformed address.
+    #[error("invalid {record:?} record value: {reason}")]
+    InvalidRecordValue { record: Record, reason: String },
+    /// the record was not written by the current owner of its domain.
+    #[error("record {0} is stale")]
+    StaleRecord(Pubkey),
+    /// the rpc request failed.
+    #[error("rpc request failed")]
+    Rpc(#[source] Box<ClientError>),
+    /// the name account has expired.
+    #[error("name account {name_account} expired at {expires_at}")]
+    Expired {
+        name_account: Pubkey,
+        expires_at: u64,
+    },
+    /// the domain is wrapped but the holder of its nft could not be found.
+    #[error("could not resolve the owner of wrapped domain nft {0}")]
+    WrappedOwnerUnresolvable(Pubkey),
+    /// an instruction of another program could not be built.
+    #[error("failed to build instruction")]
+    InvalidInstruction(#[source] ProgramError),
+    /// the instruction is not built offline as its format is not verified yet.
+    #[error("unsupported instruction: {0}")]
+    UnsupportedInstruction(String),
+}
+
+impl From<ClientError> for TldParserError {
+    fn from(error: ClientError) -> Self {
+        TldParserError::Rpc(Box::new(error))
+    }
+}
+
+pub type TldParserResult<T> = Result<T, TldParserError>;
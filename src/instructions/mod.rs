@@ -0,0 +1,10 @@
+//! Offline builders of the instructions moving domains, built on programs whose
+//! instruction format is known: Token-2022 and the associated token account program.
+//!
+//! ANS, tld house and name house instructions are not built until their format is
+//! checked against the program IDL or a recorded transaction, see tests/fixtures/README.md.
+
+mod transfer;
+mod wrapped_domain;
+
+pub use {transfer::*, wrapped_domain::*};
@@ -0,0 +1,71 @@
+use {
+    crate::{
+        error::{TldParserError, TldParserResult},
+        instructions::WrappedDomainAccounts,
+        name_record_handler::get_domain_key,
+        types::{DomainName, DomainResolution},
+        utils::is_wrapped_domain,
+    },
+    solana_sdk::{instruction::Instruction, pubkey::Pubkey},
+    spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+};
+
+/// Returns the instructions giving a domain e.g. "miester.abc" or a subdomain to `new_owner`.
+///
+/// `resolution` is the current resolution of the domain, see `TldParser::resolve_domain`.
+/// only wrapped domains are supported: an unwrapped domain is transferred by ANS, whose
+/// transfer instruction is not verified yet, and fails with `UnsupportedInstruction`.
+/// a wrapped domain, owned by its nft record, is transferred by sending its Token-2022 nft
+/// from the token account found by the resolution, signed by its holder who also pays
+/// for the token account of `new_owner`.
+pub fn transfer_domain(
+    domain_tld: &DomainName,
+    resolution: &DomainResolution,
+    new_owner: &Pubkey,
+) -> TldParserResult<Vec<Instruction>> {
+    if domain_tld.is_tld() || domain_tld.record().is_some() {
+        return Err(TldParserError::InvalidDomain(format!(
+            "{} is not a domain or subdomain",
+            domain_tld
+        )));
+    }
+    let name_account = get_domain_key(&domain_tld.to_string(), false)?.pubkey;
+    let current_owner = &resolution.owner;
+    if !is_wrapped_domain(
+        &name_account,
+        domain_tld.tld(),
+        &resolution.name_record.owner,
+    ) {
+        return Err(TldParserError::UnsupportedInstruction(format!(
+            "{} is not wrapped, ANS transfers are not supported",
+            domain_tld
+        )));
+    }
+
+    let accounts = WrappedDomainAccounts::new(domain_tld)?;
+    let token_program = spl_token_2022::id();
+    let source = resolution
+        .token_account
+        .ok_or(TldParserError::WrappedOwnerUnresolvable(accounts.mint))?;
+    let destination = accounts.token_account(new_owner);
+    let transfer = spl_token_2022::instruction::transfer_checked(
+        &token_program,
+        &source,
+        &accounts.mint,
+        &destination,
+        current_owner,
+        &[],
+        1,
+        0,
+    )
+    .map_err(TldParserError::InvalidInstruction)?;
+    Ok(vec![
+        create_associated_token_account_idempotent(
+            current_owner,
+            new_owner,
+            &accounts.mint,
+            &token_program,
+        ),
+        transfer,
+    ])
+}
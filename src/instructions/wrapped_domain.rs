@@ -0,0 +1,54 @@
+use {
+    crate::{
+        error::{TldParserError, TldParserResult},
+        name_record_handler::get_domain_key,
+        pda::*,
+        types::DomainName,
+    },
+    solana_sdk::pubkey::Pubkey,
+    spl_associated_token_account::get_associated_token_address_with_program_id,
+};
+
+/**
+ * Accounts of a wrapped domain, derived from its name and tld.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct WrappedDomainAccounts {
+    pub name_account: Pubkey,
+    pub tld_house: Pubkey,
+    pub name_house: Pubkey,
+    /// owner of the name record while the domain is wrapped.
+    pub nft_record: Pubkey,
+    /// Token-2022 mint of the nft.
+    pub mint: Pubkey,
+    /// collection mint of the tld.
+    pub collection_mint: Pubkey,
+}
+
+impl WrappedDomainAccounts {
+    /// derives the accounts of a domain e.g. "miester.abc", subdomains cannot be wrapped.
+    pub fn new(domain_tld: &DomainName) -> TldParserResult<Self> {
+        if domain_tld.domain().is_none() || domain_tld.is_subdomain() {
+            return Err(TldParserError::InvalidDomain(format!(
+                "{} is not a domain",
+                domain_tld
+            )));
+        }
+        let name_account = get_domain_key(&domain_tld.to_string(), false)?.pubkey;
+        let (tld_house, _) = find_tld_house(domain_tld.tld());
+        let (name_house, _) = find_name_house(&tld_house);
+        Ok(Self {
+            name_account,
+            tld_house,
+            name_house,
+            nft_record: find_nft_record(&name_account, &name_house).0,
+            mint: find_mint_address(&name_account, &name_house).0,
+            collection_mint: find_collection_mint_address(&tld_house).0,
+        })
+    }
+
+    /// Token-2022 associated token account of the nft for `wallet`.
+    pub fn token_account(&self, wallet: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(wallet, &self.mint, &spl_token_2022::id())
+    }
+}
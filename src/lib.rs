@@ -3,42 +3,90 @@
 
 use std::str::FromStr;
 
+use name_record_handler::{get_domain_key, get_records_parent_key};
+
 use {
-    solana_account_decoder::UiAccountEncoding,
+    futures::future::join_all,
     solana_client::{
-        nonblocking::rpc_client::RpcClient,
-        rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
-        rpc_filter::Memcmp,
-        rpc_filter::RpcFilterType,
+        nonblocking::rpc_client::RpcClient, rpc_filter::Memcmp, rpc_filter::RpcFilterType,
     },
-    solana_sdk::pubkey::Pubkey,
-    spl_token_2022::{extension::StateWithExtensions, state::Account},
+    solana_sdk::{account::Account, instruction::Instruction, pubkey::Pubkey},
+    spl_token_2022::{extension::StateWithExtensions, state::Account as TokenAccount},
     std::{
-        error::Error,
+        collections::{BTreeMap, HashMap},
         sync::Arc,
-        time::{SystemTime, UNIX_EPOCH},
     },
 };
+pub mod account_source;
 pub mod constants;
+pub mod error;
+pub mod instructions;
 pub mod name_record_handler;
+pub mod normalize;
 pub mod pda;
 pub mod state;
+pub mod time_source;
 pub mod types;
 pub mod utils;
-pub use {constants::*, pda::*, state::*, types::*, utils::*};
+pub use {
+    account_source::*, constants::*, error::*, normalize::*, pda::*, state::*, time_source::*,
+    types::*, utils::*,
+};
 
 /**
  * Tld Parser in for ANS Protocol in Solana blockchain.
+ * Reads accounts through an `AccountSource`, by default the nonblocking `RpcClient`,
+ * and the current time through a `TimeSource`, by default the system time.
  */
-pub struct TldParser {
-    pub rpc_client: Arc<RpcClient>,
+pub struct TldParser<S: AccountSource = RpcClient> {
+    pub account_source: Arc<S>,
+    pub time_source: Arc<dyn TimeSource>,
+    /// time in seconds an expired domain still resolves to its owner.
+    pub grace_period: u64,
 }
 
-impl TldParser {
+impl<S: AccountSource> TldParser<S> {
+    pub fn new(account_source: Arc<S>) -> Self {
+        Self {
+            account_source,
+            time_source: Arc::new(SystemTimeSource),
+            grace_period: DEFAULT_GRACE_PERIOD,
+        }
+    }
+
+    /// Sets the time source used for expiry checks, e.g. `ClockSysvarTimeSource`
+    /// to agree with the chain or `FixedTimeSource` for historical queries.
+    pub fn with_time_source(mut self, time_source: impl TimeSource + 'static) -> Self {
+        self.time_source = Arc::new(time_source);
+        self
+    }
+
+    /// Sets the grace period used for expiry checks, `DEFAULT_GRACE_PERIOD` by default.
+    pub fn with_grace_period(mut self, grace_period: u64) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+
+    /// Returns the current unix timestamp of the time source
+    pub async fn unix_timestamp(&self) -> TldParserResult<u64> {
+        self.time_source
+            .unix_timestamp(self.account_source.as_ref())
+            .await
+    }
+
+    /// Returns the expiry status of a name record at the current time
+    pub async fn validity(
+        &self,
+        name_record_header: &NameRecordHeader,
+    ) -> TldParserResult<Validity> {
+        let time_now = self.unix_timestamp().await?;
+        Ok(name_record_header.validity(time_now, self.grace_period))
+    }
+
     /// Returns ANS Main Domain from user pubkey
     /// # Example
     ///
-    /// ```
+    /// ```no_run
     /// use std::{
     ///    error::Error,
     ///    sync::Arc,
@@ -55,27 +103,25 @@ impl TldParser {
     /// #[tokio::main]
     /// async fn main () -> Result<(), Box<dyn Error>> {
     ///   let rpc_client = RpcClient::new(API_ENDPOINT.to_string());
-    ///   let parser = TldParser {
-    ///     rpc_client: Arc::new(rpc_client),
-    ///   };
+    ///   let parser = TldParser::new(Arc::new(rpc_client));
     ///   let owner = Pubkey::from_str("2EGGxj2qbNAJNgLCPKca8sxZYetyTjnoRspTPjzN2D67").unwrap();
     ///   let main_domain = parser.get_main_domain(&owner).await?;
     ///   Ok(())
     /// }
     /// ```
-    pub async fn get_main_domain(
-        &self,
-        user_address: &Pubkey,
-    ) -> Result<MainDomain, Box<dyn Error>> {
+    pub async fn get_main_domain(&self, user_address: &Pubkey) -> TldParserResult<MainDomain> {
         let (main_domain_key, _) = find_main_domain(user_address);
-        let main_domain_data = self.rpc_client.get_account_data(&main_domain_key).await?;
+        let main_domain_data = self
+            .account_source
+            .get_account_data(&main_domain_key)
+            .await?;
         let main_domain = MainDomain::deserialize_main_domain(main_domain_data.as_slice())?;
         Ok(main_domain)
     }
     /// Returns All Users ANS Domains Pubkeys from user pubkey
     /// # Example
     ///
-    /// ```
+    /// ```no_run
     /// use std::{
     ///    error::Error,
     ///    sync::Arc,
@@ -92,9 +138,7 @@ impl TldParser {
     /// #[tokio::main]
     /// async fn main () -> Result<(), Box<dyn Error>> {
     ///   let rpc_client = RpcClient::new(API_ENDPOINT.to_string());
-    ///   let parser = TldParser {
-    ///     rpc_client: Arc::new(rpc_client),
-    ///   };
+    ///   let parser = TldParser::new(Arc::new(rpc_client));
     ///   let owner = Pubkey::from_str("2EGGxj2qbNAJNgLCPKca8sxZYetyTjnoRspTPjzN2D67").unwrap();
     ///   let all_domains = parser.get_all_user_domains(&owner).await?;
     ///   Ok(())
@@ -103,30 +147,20 @@ impl TldParser {
     pub async fn get_all_user_domains(
         &self,
         user_address: &Pubkey,
-    ) -> Result<Vec<Pubkey>, Box<dyn Error>> {
+    ) -> TldParserResult<Vec<Pubkey>> {
         let memcmp = RpcFilterType::Memcmp(Memcmp::new_base58_encoded(40, user_address.as_ref()));
-        let rpc_config = RpcAccountInfoConfig {
-            encoding: Some(UiAccountEncoding::Base64),
-            data_slice: None,
-            commitment: None,
-            min_context_slot: None,
-        };
-        let config = RpcProgramAccountsConfig {
-            filters: Some(vec![memcmp]),
-            account_config: rpc_config,
-            with_context: None,
-        };
         let all_accounts = self
-            .rpc_client
-            .get_program_accounts_with_config(&ANS_PROGRAM_ID, config)
+            .account_source
+            .get_program_accounts(&ANS_PROGRAM_ID, vec![memcmp])
             .await?;
         let name_account_keys = all_accounts.into_iter().map(|(pubkey, _)| pubkey).collect();
         Ok(name_account_keys)
     }
     /// Returns All Users Domains Pubkeys for a specific tld from user pubkey
+    /// only the tld of the given name is used e.g. ".abc"
     /// # Example
     ///
-    /// ```
+    /// ```no_run
     /// use std::{
     ///    error::Error,
     ///    sync::Arc,
@@ -143,38 +177,25 @@ impl TldParser {
     /// #[tokio::main]
     /// async fn main () -> Result<(), Box<dyn Error>> {
     ///   let rpc_client = RpcClient::new(API_ENDPOINT.to_string());
-    ///   let parser = TldParser {
-    ///     rpc_client: Arc::new(rpc_client),
-    ///   };
+    ///   let parser = TldParser::new(Arc::new(rpc_client));
     ///   let owner = Pubkey::from_str("2EGGxj2qbNAJNgLCPKca8sxZYetyTjnoRspTPjzN2D67").unwrap();
-    ///   let all_domains_from_abc = parser.get_all_user_domains_from_tld(&owner, &".abc".to_string()).await?;
+    ///   let all_domains_from_abc = parser.get_all_user_domains_from_tld(&owner, &".abc".parse()?).await?;
     ///   Ok(())
     /// }
     /// ```
     pub async fn get_all_user_domains_from_tld(
         &self,
         user_address: &Pubkey,
-        tld: &String,
-    ) -> Result<Vec<Pubkey>, Box<dyn Error>> {
-        let parent_name_account = get_name_parent_from_tld(tld);
+        tld: &DomainName,
+    ) -> TldParserResult<Vec<Pubkey>> {
+        let parent_name_account = get_name_parent_from_tld(tld.tld());
         let memcmp_parent =
             RpcFilterType::Memcmp(Memcmp::new_base58_encoded(8, parent_name_account.as_ref()));
         let memcmp_user =
             RpcFilterType::Memcmp(Memcmp::new_base58_encoded(40, user_address.as_ref()));
-        let rpc_config = RpcAccountInfoConfig {
-            encoding: Some(UiAccountEncoding::Base64),
-            data_slice: None,
-            commitment: None,
-            min_context_slot: None,
-        };
-        let config = RpcProgramAccountsConfig {
-            filters: Some(vec![memcmp_parent, memcmp_user]),
-            account_config: rpc_config,
-            with_context: None,
-        };
         let all_tld_accounts = self
-            .rpc_client
-            .get_program_accounts_with_config(&ANS_PROGRAM_ID, config)
+            .account_source
+            .get_program_accounts(&ANS_PROGRAM_ID, vec![memcmp_parent, memcmp_user])
             .await?;
         let name_account_keys = all_tld_accounts
             .into_iter()
@@ -183,10 +204,11 @@ impl TldParser {
         Ok(name_account_keys)
     }
 
-    /// Returns the owner pubkey from domain name e.g. "miester.abc"
+    /// Returns the owner pubkey from domain name e.g. "miester.abc" or subdomain e.g. "sub.miester.abc"
+    /// fails with `Expired` once the domain, or the parent domain of a subdomain, is past its grace period.
     /// # Example
     ///
-    /// ```
+    /// ```no_run
     /// use std::{
     ///    error::Error,
     ///    sync::Arc,
@@ -203,75 +225,241 @@ impl TldParser {
     /// #[tokio::main]
     /// async fn main () -> Result<(), Box<dyn Error>> {
     ///   let rpc_client = RpcClient::new(API_ENDPOINT.to_string());
-    ///   let parser = TldParser {
-    ///     rpc_client: Arc::new(rpc_client),
-    ///   };
+    ///   let parser = TldParser::new(Arc::new(rpc_client));
     ///   let owner_of_domain = Pubkey::from_str("2EGGxj2qbNAJNgLCPKca8sxZYetyTjnoRspTPjzN2D67").unwrap();
-    ///   let owner = parser.get_owner_from_domain_tld(&"miester.abc".to_string()).await?;
+    ///   let owner = parser.get_owner_from_domain_tld(&"miester.abc".parse()?).await?;
     ///   assert_eq!(owner, owner_of_domain);
     ///   Ok(())
     /// }
     /// ```
     pub async fn get_owner_from_domain_tld(
         &self,
-        domain_tld: &String,
-    ) -> Result<Pubkey, Box<dyn Error>> {
-        let domain_tld_split: Vec<&str> = domain_tld.split('.').collect();
-        let domain = domain_tld_split[0];
-        let dot = ".".to_owned();
-        let tld = dot + domain_tld_split[1];
-        let parent_name_account = get_name_parent_from_tld(&tld);
-        let (name_account_key, _) =
-            find_name_account_from_name(&domain.to_string(), None, Some(&parent_name_account));
-        let name_account_data = self.rpc_client.get_account_data(&name_account_key).await?;
-        let mut name_account =
-            NameRecordHeader::deserialize_name_record(name_account_data.as_slice())?;
-        if name_account.expires_at > 0 {
-            let time_now = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
-            // grace period  = 45 days * 24 hours * 60 minutes * 60 seconds = 3_888_000 seconds
-            let grace_period = 45 * 24 * 60 * 60;
-            // added grace period = 45 days in unix_timestamp (seconds)
-            if time_now + grace_period > name_account.expires_at {
-                name_account.is_valid = false;
-                name_account.owner = Pubkey::default();
-            } else {
-                name_account.is_valid = true;
+        domain_tld: &DomainName,
+    ) -> TldParserResult<Pubkey> {
+        let resolution = self.resolve_domain(domain_tld).await?;
+        resolution.ensure_valid()?;
+        Ok(resolution.owner)
+    }
+
+    /// Returns the name account, name record, validity and owner of a domain e.g. "miester.abc"
+    /// or a subdomain e.g. "sub.miester.abc" together with those of its parent domain.
+    /// expired records resolve too, check `is_valid` or `ensure_valid`.
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::{
+    ///    error::Error,
+    ///    sync::Arc,
+    /// };
+    /// use solana_client::nonblocking::rpc_client::RpcClient;
+    /// use tldparser::TldParser;
+    ///
+    /// const API_ENDPOINT: &str = "";
+    /// #[tokio::main]
+    /// async fn main () -> Result<(), Box<dyn Error>> {
+    ///   let rpc_client = RpcClient::new(API_ENDPOINT.to_string());
+    ///   let parser = TldParser::new(Arc::new(rpc_client));
+    ///   let resolution = parser.resolve_domain(&"sub.miester.abc".parse()?).await?;
+    ///   if let Some(parent) = &resolution.parent {
+    ///     println!("{} owns miester.abc", parent.owner);
+    ///   }
+    ///   println!("{} owns sub.miester.abc, valid: {}", resolution.owner, resolution.is_valid());
+    ///   Ok(())
+    /// }
+    /// ```
+    pub async fn resolve_domain(
+        &self,
+        domain_tld: &DomainName,
+    ) -> TldParserResult<DomainResolution> {
+        let (name_account_key, parent_key) = find_domain_name_accounts(domain_tld)?;
+        let time_now = self.unix_timestamp().await?;
+        let mut resolution = self
+            .resolve_name_account(&name_account_key, domain_tld.tld(), time_now)
+            .await?;
+        if let Some(parent_key) = parent_key {
+            let parent = self
+                .resolve_name_account(&parent_key, domain_tld.tld(), time_now)
+                .await?;
+            resolution.parent = Some(Box::new(parent));
+        }
+        Ok(resolution)
+    }
+
+    /// Returns the instructions giving a wrapped domain e.g. "cicu.abc" to `new_owner`,
+    /// signed by the holder of its nft.
+    /// see `instructions::transfer_domain`, fails with `Expired` like `get_owner_from_domain_tld`.
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::{
+    ///    error::Error,
+    ///    sync::Arc,
+    /// };
+    /// use solana_sdk::pubkey;
+    /// use solana_client::nonblocking::rpc_client::RpcClient;
+    /// use tldparser::TldParser;
+    ///
+    /// const API_ENDPOINT: &str = "";
+    /// #[tokio::main]
+    /// async fn main () -> Result<(), Box<dyn Error>> {
+    ///   let rpc_client = RpcClient::new(API_ENDPOINT.to_string());
+    ///   let parser = TldParser::new(Arc::new(rpc_client));
+    ///   let new_owner = pubkey!("8XVnbeamKFPJGq1QdrMXq7ndvhiP8Dgm1HEvnLpAGP3e");
+    ///   let instructions = parser
+    ///     .transfer_domain_instructions(&"cicu.abc".parse()?, &new_owner)
+    ///     .await?;
+    ///   Ok(())
+    /// }
+    /// ```
+    pub async fn transfer_domain_instructions(
+        &self,
+        domain_tld: &DomainName,
+        new_owner: &Pubkey,
+    ) -> TldParserResult<Vec<Instruction>> {
+        let resolution = self.resolve_domain(domain_tld).await?;
+        resolution.ensure_valid()?;
+        instructions::transfer_domain(domain_tld, &resolution, new_owner)
+    }
+
+    /// Returns the owners of many domains e.g. "miester.abc" or subdomains, in the same order.
+    /// accounts are fetched in batches of `MAX_MULTIPLE_ACCOUNTS`, wrapped domains
+    /// need two more batches and one `getTokenLargestAccounts` per wrapped domain.
+    /// a domain that fails to resolve does not fail the others, only an rpc error
+    /// while fetching the batches fails the whole call.
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::{
+    ///    error::Error,
+    ///    sync::Arc,
+    /// };
+    /// use solana_client::nonblocking::rpc_client::RpcClient;
+    /// use tldparser::{DomainName, TldParser};
+    ///
+    /// const API_ENDPOINT: &str = "";
+    /// #[tokio::main]
+    /// async fn main () -> Result<(), Box<dyn Error>> {
+    ///   let rpc_client = RpcClient::new(API_ENDPOINT.to_string());
+    ///   let parser = TldParser::new(Arc::new(rpc_client));
+    ///   let domains: Vec<DomainName> = vec!["miester.abc".parse()?, "onsol.bonk".parse()?];
+    ///   for (domain, owner) in domains.iter().zip(parser.resolve_owners(&domains).await?) {
+    ///     println!("{} {:?}", domain, owner);
+    ///   }
+    ///   Ok(())
+    /// }
+    /// ```
+    pub async fn resolve_owners(
+        &self,
+        domains: &[DomainName],
+    ) -> TldParserResult<Vec<TldParserResult<Pubkey>>> {
+        let time_now = self.unix_timestamp().await?;
+        let name_account_keys: Vec<TldParserResult<(Pubkey, Option<Pubkey>)>> =
+            domains.iter().map(find_domain_name_accounts).collect();
+        let mut keys_to_fetch: Vec<Pubkey> = name_account_keys
+            .iter()
+            .filter_map(|keys| keys.as_ref().ok())
+            .flat_map(|(name_account_key, parent_key)| {
+                std::iter::once(*name_account_key).chain(*parent_key)
+            })
+            .collect();
+        keys_to_fetch.sort();
+        keys_to_fetch.dedup();
+        let name_accounts: HashMap<Pubkey, Account> = keys_to_fetch
+            .iter()
+            .zip(self.get_multiple_accounts_chunked(&keys_to_fetch).await?)
+            .filter_map(|(key, account)| Some((*key, account?)))
+            .collect();
+        // decoded per domain so a malformed account only fails the domains using it
+        let valid_name_record = |name_account_key: Pubkey| {
+            let name_account = name_accounts
+                .get(&name_account_key)
+                .ok_or(TldParserError::AccountNotFound(name_account_key))?;
+            let name_record = NameRecordHeader::deserialize_name_record(&name_account.data)?;
+            if !name_record.validity(time_now, self.grace_period).is_valid() {
+                return Err(TldParserError::Expired {
+                    name_account: name_account_key,
+                    expires_at: name_record.expires_at,
+                });
             }
+            Ok(name_record)
+        };
+
+        // owners of unwrapped domains, nft records of wrapped ones
+        let mut owners = Vec::with_capacity(domains.len());
+        let mut wrapped = Vec::new();
+        for (domain_tld, keys) in domains.iter().zip(name_account_keys) {
+            let owner = keys.and_then(|(name_account_key, parent_key)| {
+                let name_account = valid_name_record(name_account_key)?;
+                if let Some(parent_key) = parent_key {
+                    valid_name_record(parent_key)?;
+                }
+                if is_wrapped_domain(&name_account_key, domain_tld.tld(), &name_account.owner) {
+                    wrapped.push((owners.len(), name_account.owner));
+                }
+                Ok(name_account.owner)
+            });
+            owners.push(owner);
+        }
+        if wrapped.is_empty() {
+            return Ok(owners);
         }
-        let mut owner = name_account.owner;
-        let (tld_house_key, _) = find_tld_house(&tld);
-        let (name_house_key, _) = find_name_house(&tld_house_key);
-        // check whether domain is wrapped.
-        let nft_record_key = find_nft_record(&name_account_key, &name_house_key).0;
-        if owner == nft_record_key {
-            let nft_record_data_vec = self.rpc_client.get_account_data(&nft_record_key).await?;
-            let nft_record = NftRecord::from_account_info(&nft_record_data_vec)?;
-            let response =
-                get_token_largest_accounts(&self.rpc_client, &nft_record.nft_mint_account).await?;
-            let associated_token_account =
-                Pubkey::from_str(&response.value.first().unwrap().address).unwrap();
-            let associated_token_account_data = self
-                .rpc_client
-                .get_account_data(&associated_token_account)
-                .await?;
 
-            let ata_data = &associated_token_account_data;
-            if let Ok(associated_token_account_data_account) =
-                StateWithExtensions::<Account>::unpack(&ata_data)
-            {
-                owner = associated_token_account_data_account.base.owner;
+        // wrapped domains resolve to the holder of the nft
+        let nft_record_keys: Vec<Pubkey> = wrapped.iter().map(|(_, key)| *key).collect();
+        let nft_records = self.get_multiple_accounts_chunked(&nft_record_keys).await?;
+        let mut mints = Vec::with_capacity(wrapped.len());
+        for ((index, nft_record_key), nft_record_account) in wrapped.into_iter().zip(nft_records) {
+            let mint = nft_record_account
+                .ok_or(TldParserError::AccountNotFound(nft_record_key))
+                .and_then(|account| NftRecord::from_account_info(&account.data))
+                .map(|nft_record| nft_record.nft_mint_account);
+            match mint {
+                Ok(mint) => mints.push((index, mint)),
+                Err(err) => owners[index] = Err(err),
+            }
+        }
+        let largest_accounts = join_all(
+            mints
+                .iter()
+                .map(|(_, mint)| self.account_source.get_token_largest_accounts(mint)),
+        )
+        .await;
+        let mut holders = Vec::with_capacity(mints.len());
+        for ((index, mint), largest_accounts) in mints.into_iter().zip(largest_accounts) {
+            let token_account = largest_accounts.and_then(|largest_accounts| {
+                largest_accounts
+                    .first()
+                    .and_then(|largest_account| Pubkey::from_str(&largest_account.address).ok())
+                    .ok_or(TldParserError::WrappedOwnerUnresolvable(mint))
+            });
+            match token_account {
+                Ok(token_account) => holders.push((index, mint, token_account)),
+                Err(err) => owners[index] = Err(err),
             }
         }
-        Ok(owner)
+        let token_account_keys: Vec<Pubkey> = holders.iter().map(|(_, _, key)| *key).collect();
+        let token_accounts = self
+            .get_multiple_accounts_chunked(&token_account_keys)
+            .await?;
+        for ((index, mint, token_account_key), token_account) in
+            holders.into_iter().zip(token_accounts)
+        {
+            owners[index] = token_account
+                .ok_or(TldParserError::AccountNotFound(token_account_key))
+                .and_then(|account| {
+                    StateWithExtensions::<TokenAccount>::unpack(&account.data)
+                        .map(|token_account| token_account.base.owner)
+                        .map_err(|_| TldParserError::WrappedOwnerUnresolvable(mint))
+                });
+        }
+        Ok(owners)
     }
 
-    /// Returns the name_record_header from domain name e.g. "miester.abc"
+    /// Returns the name_record_header from domain name e.g. "miester.abc" or subdomain e.g. "sub.miester.abc"
+    /// a subdomain is only valid while its parent domain is.
     /// # Example
     ///
-    /// ```
+    /// ```no_run
     /// use std::{
     ///    error::Error,
     ///    sync::Arc,
@@ -288,48 +476,42 @@ impl TldParser {
     /// #[tokio::main]
     /// async fn main () -> Result<(), Box<dyn Error>> {
     ///   let rpc_client = RpcClient::new(API_ENDPOINT.to_string());
-    ///   let parser = TldParser {
-    ///     rpc_client: Arc::new(rpc_client),
-    ///   };
+    ///   let parser = TldParser::new(Arc::new(rpc_client));
     ///   let owner_of_domain = Pubkey::from_str("2EGGxj2qbNAJNgLCPKca8sxZYetyTjnoRspTPjzN2D67").unwrap();
-    ///   let name_record_header = parser.get_name_record_from_domain_tld(&"miester.abc".to_string()).await?;
+    ///   let name_record_header = parser.get_name_record_from_domain_tld(&"miester.abc".parse()?).await?;
     ///   assert_eq!(name_record_header.owner, owner_of_domain);
     ///   Ok(())
     /// }
     /// ```
     pub async fn get_name_record_from_domain_tld(
         &self,
-        domain_tld: &String,
-    ) -> Result<NameRecordHeader, Box<dyn Error>> {
-        let domain_tld_split: Vec<&str> = domain_tld.split('.').collect();
-        let domain = domain_tld_split[0];
-        let dot = ".".to_owned();
-        let tld = dot + domain_tld_split[1];
-        let parent_name_account = get_name_parent_from_tld(&tld);
-        let (name_account_key, _) =
-            find_name_account_from_name(&domain.to_string(), None, Some(&parent_name_account));
-        let name_account_data = self.rpc_client.get_account_data(&name_account_key).await?;
+        domain_tld: &DomainName,
+    ) -> TldParserResult<NameRecordHeader> {
+        let (name_account_key, parent_key) = find_domain_name_accounts(domain_tld)?;
+        let name_account_data = self
+            .account_source
+            .get_account_data(&name_account_key)
+            .await?;
         let mut name_account =
             NameRecordHeader::deserialize_name_record(name_account_data.as_slice())?;
-        if name_account.expires_at > 0 {
-            let time_now = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
-
-            // grace period  = 45 days * 24 hours * 60 minutes * 60 seconds = 3_888_000 seconds
-            let grace_period = 45 * 24 * 60 * 60;
-            if time_now + grace_period > name_account.expires_at {
-                name_account.is_valid = true
-            }
+        let time_now = self.unix_timestamp().await?;
+        let mut is_valid = name_account
+            .validity(time_now, self.grace_period)
+            .is_valid();
+        // a subdomain is only valid while its domain is
+        if let Some(parent_key) = parent_key {
+            let parent_data = self.account_source.get_account_data(&parent_key).await?;
+            let parent = NameRecordHeader::deserialize_name_record(&parent_data)?;
+            is_valid &= parent.validity(time_now, self.grace_period).is_valid();
         }
+        name_account.is_valid = is_valid;
         Ok(name_account)
     }
 
     /// Returns the name_record_header from name_account
     /// # Example
     ///
-    /// ```
+    /// ```no_run
     /// use std::{
     ///    error::Error,
     ///    sync::Arc,
@@ -346,9 +528,7 @@ impl TldParser {
     /// #[tokio::main]
     /// async fn main () -> Result<(), Box<dyn Error>> {
     ///   let rpc_client = RpcClient::new(API_ENDPOINT.to_string());
-    ///   let parser = TldParser {
-    ///     rpc_client: Arc::new(rpc_client),
-    ///   };
+    ///   let parser = TldParser::new(Arc::new(rpc_client));
     ///   let owner_of_domain = Pubkey::from_str("2EGGxj2qbNAJNgLCPKca8sxZYetyTjnoRspTPjzN2D67").unwrap();
     ///   let name_account: Pubkey = pubkey!("9YzfCEHb62bQ47snUyjkxhC9Eb6y7CSodK3m8CKWstjV");
     ///   let name_record_header = parser.get_name_record_from_name_account(&name_account).await?;
@@ -359,29 +539,17 @@ impl TldParser {
     pub async fn get_name_record_from_name_account(
         &self,
         name_account: &Pubkey,
-    ) -> Result<NameRecordHeader, Box<dyn Error>> {
-        let name_account_data = self.rpc_client.get_account_data(name_account).await?;
+    ) -> TldParserResult<NameRecordHeader> {
+        let name_account_data = self.account_source.get_account_data(name_account).await?;
         let mut name_account =
             NameRecordHeader::deserialize_name_record(name_account_data.as_slice())?;
-        if name_account.expires_at > 0 {
-            let time_now = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
-            
-            // grace period  = 45 days * 24 hours * 60 minutes * 60 seconds = 3_888_000 seconds
-            let grace_period = 45 * 24 * 60 * 60;
-
-            if time_now + grace_period > name_account.expires_at {
-                name_account.is_valid = true
-            }
-        }
+        name_account.is_valid = self.validity(&name_account).await?.is_valid();
         Ok(name_account)
     }
     /// Returns the tld from parent_name
     /// # Example
     ///
-    /// ```
+    /// ```no_run
     /// use std::{
     ///    error::Error,
     ///    sync::Arc,
@@ -398,9 +566,7 @@ impl TldParser {
     /// #[tokio::main]
     /// async fn main () -> Result<(), Box<dyn Error>> {
     ///   let rpc_client = RpcClient::new(API_ENDPOINT.to_string());
-    ///   let parser = TldParser {
-    ///     rpc_client: Arc::new(rpc_client),
-    ///   };
+    ///   let parser = TldParser::new(Arc::new(rpc_client));
     ///   let parent_name: Pubkey = pubkey!("3pSeaEVTcKLkXPCpZHDpHUMWAogYFZgKSiVtyvqcgo8a");
     ///   let tld = parser.get_tld_from_parent_account(&parent_name).await?;
     ///   assert_eq!(tld, ".abc".to_string());
@@ -410,29 +576,88 @@ impl TldParser {
     pub async fn get_tld_from_parent_account(
         &self,
         parent_account: &Pubkey,
-    ) -> Result<String, Box<dyn Error>> {
-        let name_parent_data = self.rpc_client.get_account_data(parent_account).await?;
+    ) -> TldParserResult<String> {
+        let name_parent_data = self.account_source.get_account_data(parent_account).await?;
         let name_parent = NameRecordHeader::deserialize_name_record(name_parent_data.as_slice())?;
-        let tld_house_data = self.rpc_client.get_account_data(&name_parent.owner).await?;
-        // let tld = tld_house_data[];
-        let tld_len_start = 8 + 32 + 32 + 32;
-        let tld_len_end = 8 + 32 + 32 + 32 + 4;
-        let tld_len = u32::from_le_bytes(
-            tld_house_data[tld_len_start..tld_len_end]
-                .try_into()
-                .unwrap(),
-        ) as usize;
-
-        Ok(String::from(
-            std::str::from_utf8(&tld_house_data[tld_len_end..tld_len_end + tld_len])
-                .unwrap()
-                .trim_matches(char::from(0)),
-        ))
+        let tld_house_data = self
+            .account_source
+            .get_account_data(&name_parent.owner)
+            .await?;
+        TldHouse::deserialize_tld(&tld_house_data)
     }
-    /// Returns the domain from a known name class or tld_house
+    /// Returns the tld house of a tld e.g. ".abc"
+    /// only the tld of the given name is used.
     /// # Example
     ///
+    /// ```no_run
+    /// use std::{
+    ///    error::Error,
+    ///    sync::Arc,
+    /// };
+    /// use solana_client::nonblocking::rpc_client::RpcClient;
+    /// use tldparser::TldParser;
+    ///
+    /// const API_ENDPOINT: &str = "";
+    /// #[tokio::main]
+    /// async fn main () -> Result<(), Box<dyn Error>> {
+    ///   let rpc_client = RpcClient::new(API_ENDPOINT.to_string());
+    ///   let parser = TldParser::new(Arc::new(rpc_client));
+    ///   let tld_house = parser.get_tld_house(&".abc".parse()?).await?;
+    ///   assert_eq!(tld_house.tld, ".abc".to_string());
+    ///   Ok(())
+    /// }
+    /// ```
+    pub async fn get_tld_house(&self, tld: &DomainName) -> TldParserResult<TldHouse> {
+        let (tld_house_key, _) = find_tld_house(tld.tld());
+        let tld_house_data = self.account_source.get_account_data(&tld_house_key).await?;
+        TldHouse::deserialize_tld_house(&tld_house_data)
+    }
+
+    /// Returns every tld with its tld house, parent name account and name house
+    /// read from the tld houses owned by the tld house program, sorted by tld.
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::{
+    ///    error::Error,
+    ///    sync::Arc,
+    /// };
+    /// use solana_client::nonblocking::rpc_client::RpcClient;
+    /// use tldparser::TldParser;
+    ///
+    /// const API_ENDPOINT: &str = "";
+    /// #[tokio::main]
+    /// async fn main () -> Result<(), Box<dyn Error>> {
+    ///   let rpc_client = RpcClient::new(API_ENDPOINT.to_string());
+    ///   let parser = TldParser::new(Arc::new(rpc_client));
+    ///   for tld in parser.get_all_tlds().await? {
+    ///     println!("{} {}", tld.tld, tld.tld_house);
+    ///   }
+    ///   Ok(())
+    /// }
     /// ```
+    pub async fn get_all_tlds(&self) -> TldParserResult<Vec<TldInfo>> {
+        let memcmp_discriminator = RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            0,
+            &get_account_discriminator(TldHouse::ACCOUNT_NAME),
+        ));
+        let tld_house_accounts = self
+            .account_source
+            .get_program_accounts(&TLD_HOUSE_PROGRAM_ID, vec![memcmp_discriminator])
+            .await?;
+        let mut tlds = tld_house_accounts
+            .iter()
+            .map(|(_, account)| Ok(TldInfo::new(&TldHouse::deserialize_tld(&account.data)?)))
+            .collect::<TldParserResult<Vec<_>>>()?;
+        tlds.sort_by(|a, b| a.tld.cmp(&b.tld));
+        Ok(tlds)
+    }
+
+    /// Returns the label of a domain e.g. "miester" from the tld house of its tld,
+    /// the name class of its reverse lookup. only the reverse lookup account is read.
+    /// # Example
+    ///
+    /// ```no_run
     /// use std::{
     ///    error::Error,
     ///    sync::Arc,
@@ -449,9 +674,7 @@ impl TldParser {
     /// #[tokio::main]
     /// async fn main () -> Result<(), Box<dyn Error>> {
     ///   let rpc_client = RpcClient::new(API_ENDPOINT.to_string());
-    ///   let parser = TldParser {
-    ///     rpc_client: Arc::new(rpc_client),
-    ///   };
+    ///   let parser = TldParser::new(Arc::new(rpc_client));
     ///   let name_account: Pubkey = pubkey!("9YzfCEHb62bQ47snUyjkxhC9Eb6y7CSodK3m8CKWstjV");
     ///   let (tld_house, _) = find_tld_house(&".abc".to_string());
     ///   let domain = parser.reverse_lookup_name_account_with_known_name_class(&name_account, &tld_house).await?;
@@ -463,21 +686,13 @@ impl TldParser {
         &self,
         name_account: &Pubkey,
         parent_account_owner: &Pubkey,
-    ) -> Result<String, Box<dyn Error>> {
-        let reverse_lookup_hash = get_hashed_name(&name_account.to_string());
-        let (reverse_lookup_key, _) = find_name_account_from_hashed_name(
-            &reverse_lookup_hash,
-            Some(parent_account_owner),
-            None,
-        );
+    ) -> TldParserResult<String> {
+        let (reverse_lookup_key, _) = find_reverse_lookup(name_account, parent_account_owner);
         let reverse_lookup_data = self
-            .rpc_client
+            .account_source
             .get_account_data(&reverse_lookup_key)
             .await?;
-
-        let domain_name =
-            NameRecordHeader::deserialize_reverse_lookup_domain_name(&reverse_lookup_data).unwrap();
-        Ok(domain_name)
+        NameRecordHeader::deserialize_reverse_lookup_domain_name(&reverse_lookup_data)
     }
 
     /// Returns the domain name from name_account
@@ -486,7 +701,7 @@ impl TldParser {
     ///
     /// # Example
     ///
-    /// ```
+    /// ```no_run
     /// use std::{
     ///    error::Error,
     ///    sync::Arc,
@@ -503,9 +718,7 @@ impl TldParser {
     /// #[tokio::main]
     /// async fn main () -> Result<(), Box<dyn Error>> {
     ///   let rpc_client = RpcClient::new(API_ENDPOINT.to_string());
-    ///   let parser = TldParser {
-    ///     rpc_client: Arc::new(rpc_client),
-    ///   };
+    ///   let parser = TldParser::new(Arc::new(rpc_client));
     ///   let name_account: Pubkey = pubkey!("9YzfCEHb62bQ47snUyjkxhC9Eb6y7CSodK3m8CKWstjV");
     ///   let domain = parser.reverse_lookup_name_account(&name_account).await?;
     ///   assert_eq!(domain, "miester".to_string());
@@ -515,26 +728,640 @@ impl TldParser {
     pub async fn reverse_lookup_name_account(
         &self,
         name_account: &Pubkey,
-    ) -> Result<String, Box<dyn Error>> {
-        let name_record_header = self.get_name_record_from_name_account(name_account).await?;
-        let tld = self
-            .get_tld_from_parent_account(&name_record_header.parent_name)
+    ) -> TldParserResult<String> {
+        let reverse_lookup = self.reverse_lookup(name_account).await?;
+        Ok(reverse_lookup.label)
+    }
+
+    /// Returns the domain of a name account from the tld house of its tld, the name class
+    /// of its reverse lookup, with its tld, parent and name record. the name account, tld house
+    /// and reverse lookup are fetched in one rpc call, a time source reading the `Clock`
+    /// sysvar adds one more. only for domains, use `reverse_lookup` for subdomains and records.
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::{
+    ///    error::Error,
+    ///    sync::Arc,
+    /// };
+    /// use solana_sdk::pubkey;
+    /// use solana_client::nonblocking::rpc_client::RpcClient;
+    /// use tldparser::{pda::*, TldParser};
+    ///
+    /// const API_ENDPOINT: &str = "";
+    /// #[tokio::main]
+    /// async fn main () -> Result<(), Box<dyn Error>> {
+    ///   let rpc_client = RpcClient::new(API_ENDPOINT.to_string());
+    ///   let parser = TldParser::new(Arc::new(rpc_client));
+    ///   let name_account = pubkey!("9YzfCEHb62bQ47snUyjkxhC9Eb6y7CSodK3m8CKWstjV");
+    ///   let (tld_house, _) = find_tld_house(".abc");
+    ///   let reverse_lookup = parser.reverse_lookup_with_known_name_class(&name_account, &tld_house).await?;
+    ///   assert_eq!(reverse_lookup.domain(), "miester.abc");
+    ///   Ok(())
+    /// }
+    /// ```
+    pub async fn reverse_lookup_with_known_name_class(
+        &self,
+        name_account: &Pubkey,
+        parent_account_owner: &Pubkey,
+    ) -> TldParserResult<ReverseLookup> {
+        let (reverse_lookup_key, _) = find_reverse_lookup(name_account, parent_account_owner);
+        let keys = [*name_account, *parent_account_owner, reverse_lookup_key];
+        let [name_account_data, tld_house_data, reverse_lookup_data] =
+            self.get_multiple_account_datas(keys).await?;
+        let name_record = NameRecordHeader::deserialize_name_record(&name_account_data)?;
+        self.build_reverse_lookup(
+            name_account,
+            name_record,
+            &tld_house_data,
+            &[reverse_lookup_data],
+        )
+        .await
+    }
+
+    /// Returns the domain of a name account with its tld, parent and name record
+    /// subdomains and records resolve to their full name e.g. "sub.miester.abc"
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::{
+    ///    error::Error,
+    ///    sync::Arc,
+    /// };
+    /// use solana_sdk::pubkey;
+    /// use solana_client::nonblocking::rpc_client::RpcClient;
+    /// use tldparser::TldParser;
+    ///
+    /// const API_ENDPOINT: &str = "";
+    /// #[tokio::main]
+    /// async fn main () -> Result<(), Box<dyn Error>> {
+    ///   let rpc_client = RpcClient::new(API_ENDPOINT.to_string());
+    ///   let parser = TldParser::new(Arc::new(rpc_client));
+    ///   let name_account = pubkey!("9YzfCEHb62bQ47snUyjkxhC9Eb6y7CSodK3m8CKWstjV");
+    ///   let reverse_lookup = parser.reverse_lookup(&name_account).await?;
+    ///   assert_eq!(reverse_lookup.label, "miester");
+    ///   assert_eq!(reverse_lookup.tld, ".abc");
+    ///   Ok(())
+    /// }
+    /// ```
+    pub async fn reverse_lookup(&self, name_account: &Pubkey) -> TldParserResult<ReverseLookup> {
+        let name_account_data = self.account_source.get_account_data(name_account).await?;
+        let name_record = NameRecordHeader::deserialize_name_record(&name_account_data)?;
+        // walk up the parents until the tld parent name account, owned by a tld house
+        let mut name_accounts = vec![*name_account];
+        let mut parent_account = name_record.parent_name;
+        let (tld_house, tld_house_data) = loop {
+            let name_parent_data = self
+                .account_source
+                .get_account_data(&parent_account)
+                .await?;
+            let name_parent = NameRecordHeader::deserialize_name_record(&name_parent_data)?;
+            let parent_owner = self.account_source.get_account(&name_parent.owner).await?;
+            if let Some(tld_house) =
+                parent_owner.filter(|account| account.owner == TLD_HOUSE_PROGRAM_ID)
+            {
+                // name_class
+                break (name_parent.owner, tld_house.data);
+            }
+            if name_accounts.len() == MAX_NAME_DEPTH {
+                return Err(TldParserError::InvalidDomain(format!(
+                    "{} is nested deeper than {} levels below its tld",
+                    name_account, MAX_NAME_DEPTH
+                )));
+            }
+            name_accounts.push(parent_account);
+            parent_account = name_parent.parent_name;
+        };
+        let keys: Vec<Pubkey> = name_accounts
+            .iter()
+            .map(|name_account| find_reverse_lookup(name_account, &tld_house).0)
+            .collect();
+        let accounts = self.account_source.get_multiple_accounts(&keys).await?;
+        let reverse_lookup_datas = keys
+            .into_iter()
+            .zip(accounts)
+            .map(|(key, account)| {
+                account
+                    .map(|account| account.data)
+                    .ok_or(TldParserError::AccountNotFound(key))
+            })
+            .collect::<TldParserResult<Vec<_>>>()?;
+        self.build_reverse_lookup(
+            name_account,
+            name_record,
+            &tld_house_data,
+            &reverse_lookup_datas,
+        )
+        .await
+    }
+
+    /// builds the reverse lookup from the tld house and the reverse lookups of every level
+    /// from the name account up to its domain.
+    async fn build_reverse_lookup(
+        &self,
+        name_account: &Pubkey,
+        mut name_record: NameRecordHeader,
+        tld_house_data: &[u8],
+        reverse_lookup_datas: &[Vec<u8>],
+    ) -> TldParserResult<ReverseLookup> {
+        let tld = TldHouse::deserialize_tld(tld_house_data)?;
+        let mut labels = reverse_lookup_datas
+            .iter()
+            .map(|data| NameRecordHeader::deserialize_reverse_lookup_domain_name(data))
+            .collect::<TldParserResult<Vec<String>>>()?;
+        // only the labels below the domain carry a prefix
+        let mut kind = NameKind::Domain;
+        let domain_level = labels.len().saturating_sub(1);
+        for (level, label) in labels.iter_mut().take(domain_level).enumerate() {
+            let (level_kind, bare_label) = NameKind::from_prefixed_label(label);
+            if level == 0 {
+                kind = level_kind;
+            }
+            *label = bare_label.to_string();
+        }
+        let parent_labels = labels.split_off(1.min(labels.len()));
+        name_record.is_valid = self.validity(&name_record).await?.is_valid();
+        Ok(ReverseLookup {
+            label: labels.pop().unwrap_or_default(),
+            parent_labels,
+            tld,
+            kind,
+            name_account: *name_account,
+            parent_account: name_record.parent_name,
+            name_record,
+        })
+    }
+
+    /// Returns the subdomains of a domain e.g. "sub.miester.abc" for "miester.abc"
+    /// children of the domain without a reverse lookup are left out.
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::{
+    ///    error::Error,
+    ///    sync::Arc,
+    /// };
+    /// use solana_client::nonblocking::rpc_client::RpcClient;
+    /// use tldparser::TldParser;
+    ///
+    /// const API_ENDPOINT: &str = "";
+    /// #[tokio::main]
+    /// async fn main () -> Result<(), Box<dyn Error>> {
+    ///   let rpc_client = RpcClient::new(API_ENDPOINT.to_string());
+    ///   let parser = TldParser::new(Arc::new(rpc_client));
+    ///   for subdomain in parser.get_subdomains(&"miester.abc".parse()?).await? {
+    ///     println!("{} owned by {}", subdomain, subdomain.name_record.owner);
+    ///   }
+    ///   Ok(())
+    /// }
+    /// ```
+    pub async fn get_subdomains(
+        &self,
+        domain_tld: &DomainName,
+    ) -> TldParserResult<Vec<ReverseLookup>> {
+        if domain_tld.is_tld() || domain_tld.is_subdomain() {
+            return Err(TldParserError::InvalidDomain(format!(
+                "{} is not of the form domain.tld",
+                domain_tld
+            )));
+        }
+        let (domain_key, _) = find_domain_name_accounts(domain_tld)?;
+        let children = self.get_child_names(&domain_key, domain_tld).await?;
+        Ok(children
+            .into_iter()
+            .filter(|(raw_label, _)| raw_label.starts_with(SUBDOMAIN_PREFIX))
+            .map(|(_, child)| child)
+            .collect())
+    }
+
+    /// Returns the records of a domain e.g. "url.miester.abc" for "miester.abc",
+    /// or of a subdomain e.g. "url.sub.miester.abc" for "sub.miester.abc"
+    /// records are found by the keys of `Record::ALL`, with or without a reverse lookup.
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::{
+    ///    error::Error,
+    ///    sync::Arc,
+    /// };
+    /// use solana_client::nonblocking::rpc_client::RpcClient;
+    /// use tldparser::TldParser;
+    ///
+    /// const API_ENDPOINT: &str = "";
+    /// #[tokio::main]
+    /// async fn main () -> Result<(), Box<dyn Error>> {
+    ///   let rpc_client = RpcClient::new(API_ENDPOINT.to_string());
+    ///   let parser = TldParser::new(Arc::new(rpc_client));
+    ///   for record in parser.get_records_of_domain(&"miester.abc".parse()?).await? {
+    ///     println!("{} at {}", record.label, record.name_account);
+    ///   }
+    ///   Ok(())
+    /// }
+    /// ```
+    pub async fn get_records_of_domain(
+        &self,
+        domain_tld: &DomainName,
+    ) -> TldParserResult<Vec<ReverseLookup>> {
+        let records_parent_key = get_records_parent_key(domain_tld)?;
+        let children = self
+            .get_child_names(&records_parent_key, domain_tld)
             .await?;
-        // name_class
-        let (tld_house, _) = find_tld_house(&tld);
-        let reverse_lookup_hash = get_hashed_name(&name_account.to_string());
-        let (reverse_lookup_key, _) =
-            find_name_account_from_hashed_name(&reverse_lookup_hash, Some(&tld_house), None);
-        let reverse_lookup_data = self
-            .rpc_client
-            .get_account_data(&reverse_lookup_key)
+        Ok(children
+            .into_iter()
+            .filter(|(_, child)| child.kind == NameKind::Record)
+            .map(|(_, child)| child)
+            .collect())
+    }
+
+    /// Returns every known `Record` of a domain e.g. "miester.abc" or subdomain e.g. "sub.miester.abc"
+    /// that exists, with its decoded payload and validity. the record accounts are fetched in one
+    /// rpc call, a time source reading the `Clock` sysvar adds one more. a record whose header
+    /// can not be decoded keeps its error, without failing the other records.
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::{
+    ///    error::Error,
+    ///    sync::Arc,
+    /// };
+    /// use solana_client::nonblocking::rpc_client::RpcClient;
+    /// use tldparser::{Record, TldParser};
+    ///
+    /// const API_ENDPOINT: &str = "";
+    /// #[tokio::main]
+    /// async fn main () -> Result<(), Box<dyn Error>> {
+    ///   let rpc_client = RpcClient::new(API_ENDPOINT.to_string());
+    ///   let parser = TldParser::new(Arc::new(rpc_client));
+    ///   let records = parser.get_all_records(&"miester.abc".parse()?).await?;
+    ///   if let Some(Ok(url)) = records.get(&Record::Url) {
+    ///     println!("{:?}", url.value);
+    ///   }
+    ///   Ok(())
+    /// }
+    /// ```
+    pub async fn get_all_records(
+        &self,
+        domain_tld: &DomainName,
+    ) -> TldParserResult<BTreeMap<Record, TldParserResult<RecordEntry>>> {
+        if domain_tld.is_tld() || domain_tld.record().is_some() {
+            return Err(TldParserError::InvalidDomain(format!(
+                "{} is not a domain or subdomain",
+                domain_tld
+            )));
+        }
+        let record_accounts =
+            find_domain_name_records(self.account_source.as_ref(), &domain_tld.to_string()).await?;
+        let time_now = self.unix_timestamp().await?;
+        Ok(record_accounts
+            .into_iter()
+            .map(|(record, name_account, data)| {
+                let entry = self.record_entry(record, name_account, &data, time_now);
+                (record, entry)
+            })
+            .collect())
+    }
+
+    /// Retrieves a record of a domain or subdomain e.g. `Record::Url` of "miester.abc",
+    /// checked against the current owner of the domain: a record written by a previous
+    /// owner, or before the domain was last registered, is flagged as stale.
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::{
+    ///    error::Error,
+    ///    sync::Arc,
+    /// };
+    /// use solana_client::nonblocking::rpc_client::RpcClient;
+    /// use tldparser::{Record, TldParser};
+    ///
+    /// const API_ENDPOINT: &str = "";
+    /// #[tokio::main]
+    /// async fn main () -> Result<(), Box<dyn Error>> {
+    ///   let rpc_client = RpcClient::new(API_ENDPOINT.to_string());
+    ///   let parser = TldParser::new(Arc::new(rpc_client));
+    ///   let url = parser.get_record_v2(&"miester.abc".parse()?, Record::Url).await?;
+    ///   println!("{:?}", url.ensure_fresh()?.value);
+    ///   Ok(())
+    /// }
+    /// ```
+    pub async fn get_record_v2(
+        &self,
+        domain_tld: &DomainName,
+        record: Record,
+    ) -> TldParserResult<RecordV2> {
+        if domain_tld.is_tld() || domain_tld.record().is_some() {
+            return Err(TldParserError::InvalidDomain(format!(
+                "{} is not a domain or subdomain",
+                domain_tld
+            )));
+        }
+        let name_account = get_domain_key(
+            &format!("{}.{}", get_record_string(record), domain_tld),
+            true,
+        )?
+        .pubkey;
+        let data = self.account_source.get_account_data(&name_account).await?;
+        let domain = self.resolve_domain(domain_tld).await?;
+        let time_now = self.unix_timestamp().await?;
+        let entry = self.record_entry(record, name_account, &data, time_now)?;
+        Ok(RecordV2 {
+            staleness: Staleness::of(&entry.name_record, &domain),
+            signature: NameRecordHeader::deserialize_record_signature(&data),
+            entry,
+            domain,
+        })
+    }
+
+    /// decodes a record account, with its expiry status at `time_now`.
+    fn record_entry(
+        &self,
+        record: Record,
+        name_account: Pubkey,
+        data: &[u8],
+        time_now: u64,
+    ) -> TldParserResult<RecordEntry> {
+        let mut name_record = NameRecordHeader::deserialize_name_record(data)?;
+        let validity = name_record.validity(time_now, self.grace_period);
+        name_record.is_valid = validity.is_valid();
+        Ok(RecordEntry {
+            record,
+            name_account,
+            name_record,
+            validity,
+            value: NameRecordHeader::deserialize_data_string(data),
+        })
+    }
+
+    /// resolves the name accounts under `parent_key`, with their raw prefixed label.
+    /// records are matched against the keys of `Record::ALL` under `parent_name`,
+    /// the other children are named by their reverse lookup and left out without one.
+    async fn get_child_names(
+        &self,
+        parent_key: &Pubkey,
+        parent_name: &DomainName,
+    ) -> TldParserResult<Vec<(String, ReverseLookup)>> {
+        let memcmp_parent =
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(8, parent_key.as_ref()));
+        let children = self
+            .account_source
+            .get_program_accounts(&ANS_PROGRAM_ID, vec![memcmp_parent])
+            .await?;
+        let record_keys = Record::ALL
+            .into_iter()
+            .map(|record| {
+                let record_string = get_record_string(record);
+                let key = get_domain_key(&format!("{}.{}", record_string, parent_name), true)?;
+                Ok((key.pubkey, format!("{}{}", RECORD_PREFIX, record_string)))
+            })
+            .collect::<TldParserResult<HashMap<Pubkey, String>>>()?;
+        let (tld_house_key, _) = find_tld_house(parent_name.tld());
+        let reverse_lookup_keys: Vec<Pubkey> = children
+            .iter()
+            .filter(|(name_account, _)| !record_keys.contains_key(name_account))
+            .map(|(name_account, _)| find_reverse_lookup(name_account, &tld_house_key).0)
+            .collect();
+        let mut reverse_lookups = self
+            .get_multiple_accounts_chunked(&reverse_lookup_keys)
+            .await?
+            .into_iter();
+        let parent_labels: Vec<String> = [parent_name.subdomain(), parent_name.domain()]
+            .into_iter()
+            .flatten()
+            .map(str::to_string)
+            .collect();
+        let time_now = self.unix_timestamp().await?;
+        let mut child_names = Vec::with_capacity(children.len());
+        for (name_account, account) in children {
+            let raw_label = match record_keys.get(&name_account) {
+                Some(raw_label) => raw_label.clone(),
+                None => {
+                    let Some(reverse_lookup) = reverse_lookups.next().flatten() else {
+                        continue;
+                    };
+                    let raw_label = NameRecordHeader::deserialize_reverse_lookup_domain_name(
+                        &reverse_lookup.data,
+                    )?;
+                    // a record label under a key of no known record
+                    if raw_label.starts_with(RECORD_PREFIX) {
+                        continue;
+                    }
+                    raw_label
+                }
+            };
+            let mut name_record = NameRecordHeader::deserialize_name_record(&account.data)?;
+            name_record.is_valid = name_record.validity(time_now, self.grace_period).is_valid();
+            let (kind, label) = NameKind::from_prefixed_label(&raw_label);
+            let child = ReverseLookup {
+                label: label.to_string(),
+                parent_labels: parent_labels.clone(),
+                tld: parent_name.tld().to_string(),
+                kind,
+                name_account,
+                parent_account: name_record.parent_name,
+                name_record,
+            };
+            child_names.push((raw_label, child));
+        }
+        child_names.sort_by(|a, b| a.1.label.cmp(&b.1.label).then(a.0.cmp(&b.0)));
+        Ok(child_names)
+    }
+
+    /// Returns the domain names e.g. "miester.abc" of many name accounts
+    /// name records, their parents, tld houses and reverse lookups are each fetched in batches
+    /// of `MAX_MULTIPLE_ACCOUNTS`, and every distinct parent is resolved only once.
+    /// name accounts that cannot be resolved or are not owned by the name service are left out.
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::{
+    ///    error::Error,
+    ///    sync::Arc,
+    /// };
+    /// use solana_sdk::pubkey;
+    /// use solana_client::nonblocking::rpc_client::RpcClient;
+    /// use tldparser::TldParser;
+    ///
+    /// const API_ENDPOINT: &str = "";
+    /// #[tokio::main]
+    /// async fn main () -> Result<(), Box<dyn Error>> {
+    ///   let rpc_client = RpcClient::new(API_ENDPOINT.to_string());
+    ///   let parser = TldParser::new(Arc::new(rpc_client));
+    ///   let name_account = pubkey!("9YzfCEHb62bQ47snUyjkxhC9Eb6y7CSodK3m8CKWstjV");
+    ///   let domains = parser.reverse_lookup_many(&[name_account]).await?;
+    ///   assert_eq!(domains[&name_account], "miester.abc");
+    ///   Ok(())
+    /// }
+    /// ```
+    pub async fn reverse_lookup_many(
+        &self,
+        name_accounts: &[Pubkey],
+    ) -> TldParserResult<HashMap<Pubkey, String>> {
+        let name_account_datas = self.get_multiple_accounts_chunked(name_accounts).await?;
+        let name_records: Vec<(Pubkey, NameRecordHeader)> = name_accounts
+            .iter()
+            .zip(name_account_datas)
+            .filter_map(|(name_account, account)| {
+                let account = account.filter(|account| account.owner == ANS_PROGRAM_ID)?;
+                let name_record = NameRecordHeader::deserialize_name_record(&account.data).ok()?;
+                Some((*name_account, name_record))
+            })
+            .collect();
+
+        // tld and tld house of every distinct parent
+        let mut parent_accounts: Vec<Pubkey> = name_records
+            .iter()
+            .map(|(_, name_record)| name_record.parent_name)
+            .collect();
+        parent_accounts.sort();
+        parent_accounts.dedup();
+        let parent_datas = self.get_multiple_accounts_chunked(&parent_accounts).await?;
+        let parents: Vec<(Pubkey, Pubkey)> = parent_accounts
+            .iter()
+            .zip(parent_datas)
+            .filter_map(|(parent_account, account)| {
+                let account = account.filter(|account| account.owner == ANS_PROGRAM_ID)?;
+                let name_parent = NameRecordHeader::deserialize_name_record(&account.data).ok()?;
+                Some((*parent_account, name_parent.owner))
+            })
+            .collect();
+        let tld_house_keys: Vec<Pubkey> = parents.iter().map(|(_, tld_house)| *tld_house).collect();
+        let tld_house_datas = self.get_multiple_accounts_chunked(&tld_house_keys).await?;
+        let tlds: HashMap<Pubkey, (Pubkey, String)> = parents
+            .into_iter()
+            .zip(tld_house_datas)
+            .filter_map(|((parent_account, tld_house_key), account)| {
+                let account = account.filter(|account| account.owner == TLD_HOUSE_PROGRAM_ID)?;
+                let tld = TldHouse::deserialize_tld(&account.data).ok()?;
+                Some((parent_account, (tld_house_key, tld)))
+            })
+            .collect();
+
+        // reverse lookups of the name accounts with a known tld
+        let reverse_lookups: Vec<(Pubkey, Pubkey, &str)> = name_records
+            .iter()
+            .filter_map(|(name_account, name_record)| {
+                let (tld_house, tld) = tlds.get(&name_record.parent_name)?;
+                let (reverse_lookup_key, _) = find_reverse_lookup(name_account, tld_house);
+                Some((*name_account, reverse_lookup_key, tld.as_str()))
+            })
+            .collect();
+        let reverse_lookup_keys: Vec<Pubkey> =
+            reverse_lookups.iter().map(|(_, key, _)| *key).collect();
+        let reverse_lookup_datas = self
+            .get_multiple_accounts_chunked(&reverse_lookup_keys)
             .await?;
-        let domain_len_start = 200;
-        let domain_len_end = reverse_lookup_data.len();
+        Ok(reverse_lookups
+            .into_iter()
+            .zip(reverse_lookup_datas)
+            .filter_map(|((name_account, _, tld), account)| {
+                let domain =
+                    NameRecordHeader::deserialize_reverse_lookup_domain_name(&account?.data)
+                        .ok()?;
+                Some((name_account, format!("{}{}", domain, tld)))
+            })
+            .collect())
+    }
 
-        let domain_name = String::from(
-            std::str::from_utf8(&reverse_lookup_data[domain_len_start..domain_len_end]).unwrap(),
-        );
-        Ok(domain_name)
+    /// resolves a single name record, following wrapped domains to the holder of their nft.
+    async fn resolve_name_account(
+        &self,
+        name_account_key: &Pubkey,
+        tld: &str,
+        time_now: u64,
+    ) -> TldParserResult<DomainResolution> {
+        let name_account_data = self
+            .account_source
+            .get_account_data(name_account_key)
+            .await?;
+        let mut name_record = NameRecordHeader::deserialize_name_record(&name_account_data)?;
+        let validity = name_record.validity(time_now, self.grace_period);
+        name_record.is_valid = validity.is_valid();
+        let (owner, token_account) = if validity.is_valid() {
+            self.resolve_wrapped_holder(name_account_key, tld, &name_record.owner)
+                .await?
+        } else {
+            (name_record.owner, None)
+        };
+        Ok(DomainResolution {
+            name_account: *name_account_key,
+            name_record,
+            validity,
+            owner,
+            token_account,
+            parent: None,
+        })
+    }
+
+    /// returns the holder of the nft and its token account if the name record is owned by
+    /// its nft record, else its owner.
+    async fn resolve_wrapped_holder(
+        &self,
+        name_account_key: &Pubkey,
+        tld: &str,
+        owner: &Pubkey,
+    ) -> TldParserResult<(Pubkey, Option<Pubkey>)> {
+        // check whether domain is wrapped, its owner is then the nft record.
+        if !is_wrapped_domain(name_account_key, tld, owner) {
+            return Ok((*owner, None));
+        }
+        let nft_record_key = *owner;
+        let nft_record_data_vec = self
+            .account_source
+            .get_account_data(&nft_record_key)
+            .await?;
+        let nft_record = NftRecord::from_account_info(&nft_record_data_vec)?;
+        let largest_accounts = self
+            .account_source
+            .get_token_largest_accounts(&nft_record.nft_mint_account)
+            .await?;
+        let associated_token_account = largest_accounts
+            .first()
+            .and_then(|largest_account| Pubkey::from_str(&largest_account.address).ok())
+            .ok_or(TldParserError::WrappedOwnerUnresolvable(
+                nft_record.nft_mint_account,
+            ))?;
+        let associated_token_account_data = self
+            .account_source
+            .get_account_data(&associated_token_account)
+            .await?;
+        let associated_token_account_data_account = StateWithExtensions::<TokenAccount>::unpack(
+            &associated_token_account_data,
+        )
+        .map_err(|_| TldParserError::WrappedOwnerUnresolvable(nft_record.nft_mint_account))?;
+        Ok((
+            associated_token_account_data_account.base.owner,
+            Some(associated_token_account),
+        ))
     }
+
+    /// fetches the data of a few accounts in one call, `AccountNotFound` if one does not exist.
+    async fn get_multiple_account_datas<const N: usize>(
+        &self,
+        pubkeys: [Pubkey; N],
+    ) -> TldParserResult<[Vec<u8>; N]> {
+        let accounts = self.account_source.get_multiple_accounts(&pubkeys).await?;
+        let mut datas = pubkeys.map(|_| Vec::new());
+        for ((data, pubkey), account) in datas.iter_mut().zip(pubkeys).zip(accounts) {
+            *data = account.ok_or(TldParserError::AccountNotFound(pubkey))?.data;
+        }
+        Ok(datas)
+    }
+
+    /// fetches accounts in chunks of `MAX_MULTIPLE_ACCOUNTS`, in the same order.
+    async fn get_multiple_accounts_chunked(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> TldParserResult<Vec<Option<Account>>> {
+        let mut accounts = Vec::with_capacity(pubkeys.len());
+        for chunk in pubkeys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            accounts.extend(self.account_source.get_multiple_accounts(chunk).await?);
+        }
+        Ok(accounts)
+    }
+}
+
+/// name account of a domain or subdomain, and of its parent domain for a subdomain.
+fn find_domain_name_accounts(domain_tld: &DomainName) -> TldParserResult<(Pubkey, Option<Pubkey>)> {
+    let domain_key = get_domain_key(&domain_tld.to_string(), false)?;
+    Ok((domain_key.pubkey, domain_key.parent))
 }
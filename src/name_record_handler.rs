@@ -1,70 +1,91 @@
-use std::io::Error;
-
-use crate::{constants::*, pda::*, utils::*};
+use crate::{constants::*, error::*, pda::*, types::DomainName, utils::*};
 use solana_sdk::pubkey::Pubkey;
 
-pub fn get_domain_key(domain_tld: &str, record: bool) -> Result<DomainKeyResult, Error> {
-    let domain_tld_split: Vec<&str> = domain_tld.split('.').collect();
-    if domain_tld_split.len() == 3 {
-        // handles subdomains
-        let tld = format!(".{}", domain_tld_split[2]);
-        let domain = domain_tld_split[1];
-        let sub_domain = domain_tld_split[0];
-        // parent key
-        let parent_key = _get_name_account(&tld, None).0;
-        // domain key
-        let domain_key = _get_name_account(&domain.to_string(), Some(&parent_key)).0;
-        // Sub domain
-        let prefix = if record { "1" } else { "0" };
-        let sub = format!("{}{}", prefix, sub_domain);
-        let (pubkey, hashed) = _get_name_account(&sub, Some(&domain_key));
-        return Ok(DomainKeyResult {
-            pubkey,
-            hashed,
-            is_sub: true,
-            parent: Some(domain_key),
-            is_sub_record: false,
-        });
-    } else if domain_tld_split.len() == 4 && record {
-        // handles four-level subdomain
-        let tld = format!(".{}", domain_tld_split[3]);
-        let domain = domain_tld_split[2];
-        let sub_domain = domain_tld_split[1];
-        let multi_level_sub_domain = domain_tld_split[0];
-        // parent key
-        let parent_key = _get_name_account(&tld, None).0;
-        // domain key
-        let domain_key = _get_name_account(&domain.to_string(), Some(&parent_key)).0;
-        // Sub domain has to be added when we create subdomains for users which are not records
-        let sub_key = _get_name_account(&format!("\0{}", sub_domain), Some(&domain_key)).0;
-        // Sub record
-        let record_prefix = "1";
-        let (pubkey, hashed) = _get_name_account(
-            &format!("{}{}", record_prefix, multi_level_sub_domain),
-            Some(&sub_key),
-        );
-        return Ok(DomainKeyResult {
-            pubkey,
-            hashed,
-            is_sub: true,
-            parent: Some(domain_key),
-            is_sub_record: true,
-        });
-    } else if domain_tld_split.len() > 4 {
-        panic!("Invalid derivation input, found more than 4 level subdomain");
+pub fn get_domain_key(domain_tld: &str, record: bool) -> TldParserResult<DomainKeyResult> {
+    let domain_name: DomainName = domain_tld.parse()?;
+    let domain = domain_name.domain().ok_or_else(|| {
+        TldParserError::InvalidDomain(format!("{} has no domain label", domain_tld))
+    })?;
+    // parent key
+    let parent_key = _get_name_account(domain_name.tld(), None).0;
+    // domain key
+    let (domain_key, domain_hashed) = _get_name_account(domain, Some(&parent_key));
+    match (domain_name.subdomain(), domain_name.record()) {
+        (None, _) => {
+            // just a regular domain_tld
+            Ok(DomainKeyResult {
+                pubkey: domain_key,
+                hashed: domain_hashed,
+                is_sub: false,
+                parent: None,
+                is_sub_record: false,
+            })
+        }
+        (Some(sub_domain), None) => {
+            // handles subdomains, record labels keep their case e.g. "SOL.miester.abc"
+            let sub = if record {
+                let record_label = domain_tld.split('.').next().unwrap_or(sub_domain);
+                format!("{}{}", RECORD_PREFIX, record_label)
+            } else {
+                format!("{}{}", SUBDOMAIN_PREFIX, sub_domain)
+            };
+            let (pubkey, hashed) = _get_name_account(&sub, Some(&domain_key));
+            Ok(DomainKeyResult {
+                pubkey,
+                hashed,
+                is_sub: true,
+                parent: Some(domain_key),
+                is_sub_record: false,
+            })
+        }
+        (Some(sub_domain), Some(multi_level_sub_domain)) if record => {
+            // handles four-level subdomain
+            // Sub domain has to be added when we create subdomains for users which are not records
+            let sub_key = _get_name_account(
+                &format!("{}{}", SUBDOMAIN_RECORD_PARENT_PREFIX, sub_domain),
+                Some(&domain_key),
+            )
+            .0;
+            // Sub record
+            let (pubkey, hashed) = _get_name_account(
+                &format!("{}{}", RECORD_PREFIX, multi_level_sub_domain),
+                Some(&sub_key),
+            );
+            Ok(DomainKeyResult {
+                pubkey,
+                hashed,
+                is_sub: true,
+                parent: Some(domain_key),
+                is_sub_record: true,
+            })
+        }
+        _ => Err(TldParserError::InvalidDomain(format!(
+            "{} is only valid as a record",
+            domain_tld
+        ))),
+    }
+}
+
+/// name account holding the records of a domain e.g. "miester.abc",
+/// or of a subdomain e.g. "sub.miester.abc" where records sit under its "\0" prefixed account.
+pub fn get_records_parent_key(domain_name: &DomainName) -> TldParserResult<Pubkey> {
+    let domain = domain_name.domain().ok_or_else(|| {
+        TldParserError::InvalidDomain(format!("{} has no domain label", domain_name))
+    })?;
+    let parent_key = _get_name_account(domain_name.tld(), None).0;
+    let domain_key = _get_name_account(domain, Some(&parent_key)).0;
+    match (domain_name.subdomain(), domain_name.record()) {
+        (None, _) => Ok(domain_key),
+        (Some(sub_domain), None) => Ok(_get_name_account(
+            &format!("{}{}", SUBDOMAIN_RECORD_PARENT_PREFIX, sub_domain),
+            Some(&domain_key),
+        )
+        .0),
+        _ => Err(TldParserError::InvalidDomain(format!(
+            "{} is a record, records have no records",
+            domain_name
+        ))),
     }
-    // just a regular domain_tld
-    let tld_name = format!(".{}", domain_tld_split[1]);
-    let parent_key_domain_account = _get_name_account(&tld_name, None).0;
-    let domain = domain_tld_split[0];
-    let (pubkey, hashed) = _get_name_account(&domain.to_string(), Some(&parent_key_domain_account));
-    Ok(DomainKeyResult {
-        pubkey,
-        hashed,
-        is_sub: false,
-        parent: None,
-        is_sub_record: false,
-    })
 }
 
 #[derive(Debug)]
@@ -76,7 +97,7 @@ pub struct DomainKeyResult {
     pub is_sub_record: bool,
 }
 
-fn _get_name_account(name: &String, parent: Option<&Pubkey>) -> (Pubkey, Vec<u8>) {
+fn _get_name_account(name: &str, parent: Option<&Pubkey>) -> (Pubkey, Vec<u8>) {
     let name_account;
     if parent.is_none() {
         let hashed_parentless = get_hashed_name(name);
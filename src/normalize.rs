@@ -0,0 +1,60 @@
+//! Normalization of domain labels before they are hashed.
+//!
+//! The same name can be typed as "CAFÉ", "café" (NFC) or "cafe\u{301}" (NFD),
+//! each of which would hash to a different name account. Labels are brought
+//! into a canonical form, the UTS-46 mapping in NFC with "ß" and final "ς"
+//! folded, before PDA derivation. Punycode `xn--` labels are decoded into
+//! their unicode form.
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::error::{TldParserError, TldParserResult};
+
+/// prefix of an IDNA punycode encoded label.
+pub const PUNYCODE_PREFIX: &str = "xn--";
+
+/// Returns the canonical form of a label: the UTS-46 mapping e.g. "ẞ" to "ß" and "Σ" to "σ",
+/// then "ß" folded to "ss" and "ς" to "σ" as case folding does, in unicode NFC.
+/// the zero width joiners of emoji sequences are kept, unlike UTS-46 transitional processing.
+pub fn canonicalize_label(label: &str) -> String {
+    // validation errors are left to `validate_label`, only the mapped label is used
+    let (mapped, _) = idna::Config::default().to_unicode(label);
+    mapped
+        .replace('\u{df}', "ss")
+        .replace('\u{3c2}', "\u{3c3}")
+        .nfc()
+        .collect()
+}
+
+/// Decodes an `xn--` punycode label into unicode,
+/// any other label is returned as is.
+pub fn decode_punycode_label(label: &str) -> TldParserResult<String> {
+    let prefix_len = PUNYCODE_PREFIX.len();
+    match label.get(..prefix_len) {
+        Some(prefix) if prefix.eq_ignore_ascii_case(PUNYCODE_PREFIX) => {
+            idna::punycode::decode_to_string(&label[prefix_len..]).ok_or_else(|| {
+                TldParserError::InvalidDomain(format!("{} is not valid punycode", label))
+            })
+        }
+        _ => Ok(label.to_string()),
+    }
+}
+
+/// Encodes a non-ascii label into `xn--` punycode,
+/// ascii labels are returned as is.
+pub fn encode_punycode_label(label: &str) -> TldParserResult<String> {
+    if label.is_ascii() {
+        return Ok(label.to_string());
+    }
+    idna::punycode::encode_str(label)
+        .map(|encoded| format!("{}{}", PUNYCODE_PREFIX, encoded))
+        .ok_or_else(|| {
+            TldParserError::InvalidDomain(format!("{} can not be encoded as punycode", label))
+        })
+}
+
+/// Returns the canonical form of a label typed by a user,
+/// decoding punycode and applying [`canonicalize_label`].
+pub fn normalize_label(label: &str) -> TldParserResult<String> {
+    Ok(canonicalize_label(&decode_punycode_label(label)?))
+}
@@ -1,18 +1,20 @@
 use solana_sdk::pubkey::Pubkey;
 
-use crate::{constants::*, utils::*};
+use crate::{constants::*, normalize::canonicalize_label, utils::*};
 
 pub fn find_tld_state() -> (Pubkey, u8) {
     let tld_house_seeds = &[PDA_SEED.as_bytes()];
     Pubkey::find_program_address(tld_house_seeds, &TLD_HOUSE_PROGRAM_ID)
 }
 
-pub fn find_tld_house(tld: &String) -> (Pubkey, u8) {
+pub fn find_tld_house(tld: &str) -> (Pubkey, u8) {
+    let tld = canonicalize_label(tld);
     let tld_house_seeds = &[PREFIX.as_bytes(), tld.as_bytes()];
     Pubkey::find_program_address(tld_house_seeds, &TLD_HOUSE_PROGRAM_ID)
 }
 
-pub fn find_tld_house_treasury(tld: &String) -> (Pubkey, u8) {
+pub fn find_tld_house_treasury(tld: &str) -> (Pubkey, u8) {
+    let tld = canonicalize_label(tld);
     let tld_treasury_seeds = &[PREFIX.as_bytes(), tld.as_bytes(), TREASURY.as_bytes()];
     Pubkey::find_program_address(tld_treasury_seeds, &TLD_HOUSE_PROGRAM_ID)
 }
@@ -65,7 +67,7 @@ pub fn find_collection_mint_address(tld_house: &Pubkey) -> (Pubkey, u8) {
 }
 
 pub fn find_name_account_from_hashed_name(
-    hashed_name: &Vec<u8>,
+    hashed_name: &[u8],
     name_class_opt: Option<&Pubkey>,
     name_parent_opt: Option<&Pubkey>,
 ) -> (Pubkey, u8) {
@@ -81,7 +83,7 @@ pub fn find_name_account_from_hashed_name(
 }
 
 pub fn find_name_account_from_name(
-    name: &String,
+    name: &str,
     name_class_opt: Option<&Pubkey>,
     name_parent_opt: Option<&Pubkey>,
 ) -> (Pubkey, u8) {
@@ -92,3 +94,10 @@ pub fn find_name_account_from_name(
 
     Pubkey::find_program_address(checked_account_seeds, &ANS_PROGRAM_ID)
 }
+
+/// reverse lookup account of a name account, holding its domain label.
+/// the name class is the tld house of the domain.
+pub fn find_reverse_lookup(name_account: &Pubkey, name_class: &Pubkey) -> (Pubkey, u8) {
+    let hashed_name = get_hashed_name(&name_account.to_string());
+    find_name_account_from_hashed_name(&hashed_name, Some(name_class), None)
+}
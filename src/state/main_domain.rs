@@ -1,6 +1,7 @@
 use anchor_lang::AnchorDeserialize;
 use solana_sdk::pubkey::Pubkey;
-use std::io::Error;
+
+use crate::{error::TldParserResult, utils::account_data_from};
 
 /**
  * ANS Main domain
@@ -20,8 +21,8 @@ impl MainDomain {
 
     /// deserializes the main domain if it exists.
     /// will throw an error due to deserialization error.
-    pub fn deserialize_main_domain(src: &[u8]) -> Result<MainDomain, Error> {
-        let mut p = &src[8..];
+    pub fn deserialize_main_domain(src: &[u8]) -> TldParserResult<MainDomain> {
+        let mut p = account_data_from(src, 8)?;
         let name_record_header = MainDomain::deserialize(&mut p)?;
         Ok(name_record_header)
     }
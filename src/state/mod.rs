@@ -1,4 +1,5 @@
 mod main_domain;
 mod name_record_header;
 mod nft_record;
-pub use {main_domain::*, name_record_header::*, nft_record::*};
+mod tld_house;
+pub use {main_domain::*, name_record_header::*, nft_record::*, tld_house::*};
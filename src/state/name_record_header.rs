@@ -1,7 +1,7 @@
-use std::io::Error;
-
 use anchor_lang::AnchorDeserialize;
-use solana_sdk::pubkey::Pubkey;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+
+use crate::{error::TldParserResult, types::Validity, utils::*};
 
 /**
  * Name Record Header: home of the name accounts.
@@ -29,37 +29,64 @@ pub struct NameRecordHeader {
     pub created_at: u64,
     pub non_transferable: bool,
     /// is the name account data valid (not expired)
+    /// set from `validity` by the `TldParser` name record getters.
     pub is_valid: bool,
     // data sits here owner/parent name owner can add as many data as they please.
 }
 
-impl<'a> NameRecordHeader {
-    pub const HASH_PREFIX: &'a str = "ALT Name Service";
+impl NameRecordHeader {
+    pub const HASH_PREFIX: &'static str = "ALT Name Service";
     pub const LEN: usize = 200;
+    /// length of the ed25519 signature that may follow the data string of a record.
+    pub const SIGNATURE_LEN: usize = 64;
+    /// bytes announcing the signature after the data string of a record, see `get_record_payload`.
+    pub const SIGNATURE_MARKER: &'static [u8] = b"ed25519:";
 
     /// deserializes the name record header if it exists.
     /// will throw an error due to deserialization error.
-    pub fn deserialize_name_record(src: &[u8]) -> Result<NameRecordHeader, Error> {
-        let mut p = &src[8..];
+    pub fn deserialize_name_record(src: &[u8]) -> TldParserResult<NameRecordHeader> {
+        let mut p = account_data_from(src, 8)?;
         let name_record_header = NameRecordHeader::deserialize(&mut p)?;
         Ok(name_record_header)
     }
 
     /// deserialized data if it exists.
-    /// will throw an error due to len not being found.
-    pub fn deserialize_data_string(src: &[u8]) -> Result<String, Error> {
-        let p = &src[Self::LEN..];
-        let len = u32::from_le_bytes(p[0..4].try_into().unwrap()) as usize;
+    /// will throw an error if the data is too short or not valid utf-8.
+    pub fn deserialize_data_string(src: &[u8]) -> TldParserResult<String> {
+        let domain_data = read_length_prefixed_string(src, Self::LEN)?;
+        Ok(domain_data.to_string())
+    }
 
-        let domain_data = String::from(std::str::from_utf8(&p[4..4 + len]).unwrap());
-        Ok(domain_data)
+    /// deserialized signature following `SIGNATURE_MARKER` after the data string, if any.
+    /// bytes after the data string that do not start with the marker are not a signature.
+    /// will throw an error if the data is too short, including a signature cut short.
+    pub fn deserialize_record_signature(src: &[u8]) -> TldParserResult<Option<Signature>> {
+        let len_bytes = account_data_slice(src, Self::LEN, Self::LEN + 4)?;
+        let len =
+            u32::from_le_bytes([len_bytes[0], len_bytes[1], len_bytes[2], len_bytes[3]]) as usize;
+        let marker = Self::LEN + 4 + len;
+        if !account_data_from(src, marker)?.starts_with(Self::SIGNATURE_MARKER) {
+            return Ok(None);
+        }
+        let start = marker + Self::SIGNATURE_MARKER.len();
+        let mut signature = [0; Self::SIGNATURE_LEN];
+        signature.copy_from_slice(account_data_slice(src, start, start + Self::SIGNATURE_LEN)?);
+        if signature == [0; Self::SIGNATURE_LEN] {
+            return Ok(None);
+        }
+        Ok(Some(Signature::from(signature)))
     }
 
     /// deserialized reverse lookup domain name if it exists.
-    /// will throw an error due to len not being found.
-    pub fn deserialize_reverse_lookup_domain_name(src: &[u8]) -> Result<String, Error> {
-        let p = &src[Self::LEN..src.len()];
-        let domain_data = String::from(std::str::from_utf8(&p).unwrap());
-        Ok(domain_data)
+    /// will throw an error if the data is too short or not valid utf-8.
+    pub fn deserialize_reverse_lookup_domain_name(src: &[u8]) -> TldParserResult<String> {
+        let p = account_data_from(src, Self::LEN)?;
+        let domain_data = std::str::from_utf8(p)?.trim_end_matches(char::from(0));
+        Ok(domain_data.to_string())
+    }
+
+    /// expiry status of the record at the unix timestamp `now`.
+    pub fn validity(&self, now: u64, grace_period: u64) -> Validity {
+        Validity::from_expires_at(self.expires_at, now, grace_period)
     }
 }
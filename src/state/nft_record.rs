@@ -1,8 +1,8 @@
-use std::io::Error;
-
 use anchor_lang::AnchorDeserialize;
 use solana_sdk::pubkey::Pubkey;
 
+use crate::{error::TldParserResult, utils::account_data_from};
+
 #[derive(AnchorDeserialize, Clone, PartialEq, Eq, Default, Debug)]
 pub enum Tag {
     Uninitialized,
@@ -32,13 +32,12 @@ pub struct NftRecord {
     pub tld_house: Pubkey,
 }
 
-impl<'a> NftRecord {
-    pub const PREFIX: &'a [u8; 10] = b"nft_record";
+impl NftRecord {
+    pub const PREFIX: &'static [u8; 10] = b"nft_record";
     pub const LEN: usize = 8 + 1 + 1 + 32 + 32 + 32 + 32 + 64;
 
-    pub fn from_account_info(data_vec: &Vec<u8>) -> Result<NftRecord, Error> {
-        let data = &data_vec as &[u8];
-        let result = NftRecord::deserialize(&mut &data[8..])?;
+    pub fn from_account_info(data: &[u8]) -> TldParserResult<NftRecord> {
+        let result = NftRecord::deserialize(&mut account_data_from(data, 8)?)?;
         Ok(result)
     }
 
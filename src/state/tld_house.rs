@@ -0,0 +1,52 @@
+use anchor_lang::AnchorDeserialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    error::TldParserResult,
+    utils::{check_account_discriminator, read_length_prefixed_string},
+};
+
+/**
+ * Tld House: configuration of a tld, owner of the tld parent name account.
+ *
+ * Only the fields up to `tld` are decoded, the layout known to match mainnet accounts.
+ * the pricing and minting configuration after it is left out until checked against
+ * a recorded account.
+ */
+#[derive(Clone, Debug, AnchorDeserialize, Eq, PartialEq)]
+pub struct TldHouse {
+    /// authority allowed to update the tld house.
+    pub authority: Pubkey,
+
+    /// treasury receiving the registration fees.
+    pub treasury: Pubkey,
+
+    /// manager allowed to withdraw from the treasury.
+    pub treasury_manager: Pubkey,
+
+    /// tld of the house including its dot e.g. ".abc"
+    pub tld: String,
+}
+
+impl TldHouse {
+    pub const ACCOUNT_NAME: &'static str = "TldHouse";
+    /// offset of the length prefixed tld, after the discriminator, authority, treasury
+    /// and treasury manager.
+    pub const TLD_OFFSET: usize = 8 + 32 + 32 + 32;
+
+    /// reads only the tld e.g. ".abc" of a tld house, whatever follows it.
+    /// will throw an error if the data is too short or not valid utf-8.
+    pub fn deserialize_tld(src: &[u8]) -> TldParserResult<String> {
+        let tld = read_length_prefixed_string(src, Self::TLD_OFFSET)?;
+        Ok(tld.trim_matches(char::from(0)).to_string())
+    }
+
+    /// deserializes the known fields of the tld house if it exists, ignoring the rest.
+    /// will throw an error if the discriminator does not match or due to deserialization error.
+    pub fn deserialize_tld_house(src: &[u8]) -> TldParserResult<TldHouse> {
+        let mut p = check_account_discriminator(src, Self::ACCOUNT_NAME)?;
+        let mut tld_house = TldHouse::deserialize(&mut p)?;
+        tld_house.tld = tld_house.tld.trim_matches(char::from(0)).to_string();
+        Ok(tld_house)
+    }
+}
@@ -0,0 +1,68 @@
+use {
+    crate::{account_source::AccountSource, error::*},
+    async_trait::async_trait,
+    solana_sdk::{
+        account::from_account,
+        sysvar::{self, clock::Clock},
+    },
+    std::{
+        io::{self, ErrorKind},
+        time::{SystemTime, UNIX_EPOCH},
+    },
+};
+
+/**
+ * Source of the current time used for expiry checks.
+ *
+ * `SystemTimeSource` reads the local clock, `FixedTimeSource` always returns
+ * the same timestamp and `ClockSysvarTimeSource` reads the on-chain `Clock`
+ * sysvar through the parser's account source.
+ */
+#[async_trait]
+pub trait TimeSource: Send + Sync {
+    /// Returns the current unix timestamp in seconds.
+    async fn unix_timestamp(&self, account_source: &dyn AccountSource) -> TldParserResult<u64>;
+}
+
+/// Local system time, the default time source.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemTimeSource;
+
+#[async_trait]
+impl TimeSource for SystemTimeSource {
+    async fn unix_timestamp(&self, _account_source: &dyn AccountSource) -> TldParserResult<u64> {
+        Ok(SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs())
+    }
+}
+
+/// A fixed unix timestamp, for tests and historical queries.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FixedTimeSource(pub u64);
+
+#[async_trait]
+impl TimeSource for FixedTimeSource {
+    async fn unix_timestamp(&self, _account_source: &dyn AccountSource) -> TldParserResult<u64> {
+        Ok(self.0)
+    }
+}
+
+/// Cluster time from the `Clock` sysvar, agrees with what the programs see.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ClockSysvarTimeSource;
+
+#[async_trait]
+impl TimeSource for ClockSysvarTimeSource {
+    async fn unix_timestamp(&self, account_source: &dyn AccountSource) -> TldParserResult<u64> {
+        let clock_account = account_source
+            .get_account(&sysvar::clock::id())
+            .await?
+            .ok_or(TldParserError::AccountNotFound(sysvar::clock::id()))?;
+        let clock: Clock = from_account(&clock_account).ok_or_else(|| {
+            io::Error::new(ErrorKind::InvalidData, "invalid clock sysvar account data")
+        })?;
+        Ok(u64::try_from(clock.unix_timestamp).unwrap_or_default())
+    }
+}
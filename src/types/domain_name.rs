@@ -0,0 +1,175 @@
+use std::{fmt, str::FromStr};
+
+use crate::{
+    error::{TldParserError, TldParserResult},
+    normalize::*,
+};
+
+/**
+ * A parsed and normalized ANS domain name.
+ *
+ * Accepts ".tld", "domain.tld", "subdomain.domain.tld" and
+ * "record.subdomain.domain.tld". Labels are decoded from punycode and
+ * brought into their canonical form (see `canonicalize_label`), except for
+ * the record label of a four level name which is kept as is.
+ */
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct DomainName {
+    /// tld including its leading dot e.g. ".abc"
+    tld: String,
+    /// second level label e.g. "miester" in "miester.abc"
+    domain: Option<String>,
+    /// third level label e.g. "sub" in "sub.miester.abc"
+    subdomain: Option<String>,
+    /// fourth level record label e.g. "SOL" in "SOL.sub.miester.abc"
+    record: Option<String>,
+}
+
+impl DomainName {
+    /// maximum length of a single label in bytes.
+    pub const MAX_LABEL_LEN: usize = 63;
+    /// maximum length of the full name in bytes.
+    pub const MAX_LEN: usize = 253;
+
+    /// tld including its leading dot e.g. ".abc"
+    pub fn tld(&self) -> &str {
+        &self.tld
+    }
+
+    /// second level label e.g. "miester" in "miester.abc"
+    pub fn domain(&self) -> Option<&str> {
+        self.domain.as_deref()
+    }
+
+    /// third level label e.g. "sub" in "sub.miester.abc"
+    pub fn subdomain(&self) -> Option<&str> {
+        self.subdomain.as_deref()
+    }
+
+    /// fourth level record label e.g. "SOL" in "SOL.sub.miester.abc"
+    pub fn record(&self) -> Option<&str> {
+        self.record.as_deref()
+    }
+
+    /// true for a bare tld e.g. ".abc"
+    pub fn is_tld(&self) -> bool {
+        self.domain.is_none()
+    }
+
+    /// true for "subdomain.domain.tld" and deeper names.
+    pub fn is_subdomain(&self) -> bool {
+        self.subdomain.is_some()
+    }
+
+    /// returns the name with every non-ascii label encoded as punycode
+    /// e.g. "xn--caf-dma.abc" for "café.abc"
+    pub fn to_ascii(&self) -> TldParserResult<String> {
+        let labels = [&self.record, &self.subdomain, &self.domain]
+            .into_iter()
+            .flatten()
+            .map(|label| encode_punycode_label(label))
+            .collect::<TldParserResult<Vec<String>>>()?;
+        let tld = encode_punycode_label(&self.tld[1..])?;
+        if labels.is_empty() {
+            return Ok(format!(".{}", tld));
+        }
+        Ok(format!("{}.{}", labels.join("."), tld))
+    }
+}
+
+/// checks a single label for emptiness, length and illegal characters.
+pub(crate) fn validate_label(label: &str) -> TldParserResult<()> {
+    if label.is_empty() {
+        return Err(TldParserError::InvalidDomain("empty label".to_string()));
+    }
+    if label.len() > DomainName::MAX_LABEL_LEN {
+        return Err(TldParserError::InvalidDomain(format!(
+            "label {} is longer than {} bytes",
+            label,
+            DomainName::MAX_LABEL_LEN
+        )));
+    }
+    let is_illegal = |c: char| {
+        c.is_whitespace() || c.is_control() || (c.is_ascii_punctuation() && c != '-' && c != '_')
+    };
+    if let Some(c) = label.chars().find(|c| is_illegal(*c)) {
+        return Err(TldParserError::InvalidDomain(format!(
+            "label {} contains illegal character {:?}",
+            label, c
+        )));
+    }
+    Ok(())
+}
+
+impl FromStr for DomainName {
+    type Err = TldParserError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        if name.len() > DomainName::MAX_LEN {
+            return Err(TldParserError::InvalidDomain(format!(
+                "{} is longer than {} bytes",
+                name,
+                DomainName::MAX_LEN
+            )));
+        }
+        // ".abc" is a bare tld
+        if let Some(tld) = name.strip_prefix('.') {
+            let tld = normalize_label(tld)?;
+            validate_label(&tld)?;
+            return Ok(DomainName {
+                tld: format!(".{}", tld),
+                domain: None,
+                subdomain: None,
+                record: None,
+            });
+        }
+        let labels: Vec<&str> = name.split('.').collect();
+        let normalized = labels
+            .iter()
+            .map(|label| normalize_label(label))
+            .collect::<TldParserResult<Vec<String>>>()?;
+        for label in &normalized {
+            validate_label(label)?;
+        }
+        let domain_name = match (&labels[..], &normalized[..]) {
+            (_, [domain, tld]) => DomainName {
+                tld: format!(".{}", tld),
+                domain: Some(domain.clone()),
+                subdomain: None,
+                record: None,
+            },
+            (_, [subdomain, domain, tld]) => DomainName {
+                tld: format!(".{}", tld),
+                domain: Some(domain.clone()),
+                subdomain: Some(subdomain.clone()),
+                record: None,
+            },
+            ([record, ..], [_, subdomain, domain, tld]) => DomainName {
+                tld: format!(".{}", tld),
+                domain: Some(domain.clone()),
+                subdomain: Some(subdomain.clone()),
+                record: Some(record.to_string()),
+            },
+            _ => {
+                return Err(TldParserError::InvalidDomain(format!(
+                    "{} has {} labels, expected .tld or between 2 and 4",
+                    name,
+                    labels.len()
+                )))
+            }
+        };
+        Ok(domain_name)
+    }
+}
+
+impl fmt::Display for DomainName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for label in [&self.record, &self.subdomain].into_iter().flatten() {
+            write!(f, "{}.", label)?;
+        }
+        if let Some(domain) = &self.domain {
+            write!(f, "{}", domain)?;
+        }
+        write!(f, "{}", self.tld)
+    }
+}
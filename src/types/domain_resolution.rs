@@ -0,0 +1,52 @@
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    error::{TldParserError, TldParserResult},
+    state::NameRecordHeader,
+    types::Validity,
+};
+
+/**
+ * Result of a forward resolution of a domain or subdomain.
+ *
+ * For a subdomain e.g. "sub.miester.abc", `parent` holds the resolution of
+ * its domain "miester.abc": a subdomain is only valid while its domain is.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DomainResolution {
+    /// name account of the domain or subdomain.
+    pub name_account: Pubkey,
+    /// name record of the domain or subdomain.
+    pub name_record: NameRecordHeader,
+    /// expiry status of the name record itself.
+    pub validity: Validity,
+    /// owner of the name record, the holder of the nft if it is wrapped.
+    /// the raw name record owner if the record has expired.
+    pub owner: Pubkey,
+    /// token account holding the nft of a wrapped domain, as found by `getTokenLargestAccounts`.
+    /// not necessarily the associated token account of `owner`.
+    pub token_account: Option<Pubkey>,
+    /// resolution of the parent domain of a subdomain.
+    pub parent: Option<Box<DomainResolution>>,
+}
+
+impl DomainResolution {
+    /// whether the record and, for a subdomain, its parent domain have not expired.
+    pub fn is_valid(&self) -> bool {
+        self.validity.is_valid() && self.parent.as_ref().is_none_or(|parent| parent.is_valid())
+    }
+
+    /// fails with `Expired` for the first expired record, the subdomain before its parent.
+    pub fn ensure_valid(&self) -> TldParserResult<()> {
+        if !self.validity.is_valid() {
+            return Err(TldParserError::Expired {
+                name_account: self.name_account,
+                expires_at: self.name_record.expires_at,
+            });
+        }
+        match &self.parent {
+            Some(parent) => parent.ensure_valid(),
+            None => Ok(()),
+        }
+    }
+}
@@ -1,2 +1,13 @@
+mod domain_name;
+mod domain_resolution;
+mod record_entry;
+mod record_v2;
+mod record_value;
 mod records;
-pub use records::*;
+mod reverse_lookup;
+mod tld_info;
+mod validity;
+pub use {
+    domain_name::*, domain_resolution::*, record_entry::*, record_v2::*, record_value::*,
+    records::*, reverse_lookup::*, tld_info::*, validity::*,
+};
@@ -0,0 +1,37 @@
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    error::{TldParserError, TldParserResult},
+    state::NameRecordHeader,
+    types::*,
+};
+
+/**
+ * A record account of a domain e.g. "url.miester.abc" and its decoded payload.
+ */
+#[derive(Debug)]
+pub struct RecordEntry {
+    /// the record e.g. `Record::Url`
+    pub record: Record,
+    /// name account of the record.
+    pub name_account: Pubkey,
+    /// name record header of the record account.
+    pub name_record: NameRecordHeader,
+    /// expiry status of the record account itself.
+    pub validity: Validity,
+    /// the record payload, or why it could not be decoded.
+    pub value: TldParserResult<String>,
+}
+
+impl RecordEntry {
+    /// Parses and validates the payload for its record, see `RecordValue::parse`.
+    pub fn parse_value(&self) -> TldParserResult<RecordValue> {
+        match &self.value {
+            Ok(value) => RecordValue::parse(self.record, value),
+            Err(error) => Err(TldParserError::InvalidRecordValue {
+                record: self.record,
+                reason: error.to_string(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+
+use crate::{
+    error::{TldParserError, TldParserResult},
+    state::NameRecordHeader,
+    types::*,
+};
+
+/**
+ * Whether a record was written by the current owner of its domain.
+ *
+ * Only a change of the name record owner, or a re-registration recreating the
+ * name account, is detected: a transfer keeps `created_at`, so a record written
+ * before the domain went from A to B and back to A is `Fresh` again.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Staleness {
+    /// written by the current owner since the name account of the domain was created.
+    Fresh,
+    /// written by someone else e.g. the owner before a transfer.
+    OwnerChanged { record_owner: Pubkey },
+    /// created before the name account of the domain e.g. before it expired and was registered again.
+    PredatesDomain {
+        created_at: u64,
+        domain_created_at: u64,
+    },
+}
+
+impl Staleness {
+    /// compares the header of a record with the resolution of its domain.
+    /// a wrapped domain accepts records of both the nft holder and the nft record.
+    pub fn of(record: &NameRecordHeader, domain: &DomainResolution) -> Self {
+        if record.owner != domain.owner && record.owner != domain.name_record.owner {
+            Staleness::OwnerChanged {
+                record_owner: record.owner,
+            }
+        } else if record.created_at < domain.name_record.created_at {
+            Staleness::PredatesDomain {
+                created_at: record.created_at,
+                domain_created_at: domain.name_record.created_at,
+            }
+        } else {
+            Staleness::Fresh
+        }
+    }
+
+    pub fn is_stale(&self) -> bool {
+        *self != Staleness::Fresh
+    }
+}
+
+/**
+ * A record checked against the current owner of its domain, see `TldParser::get_record_v2`.
+ *
+ * The payload of a record may be followed by `NameRecordHeader::SIGNATURE_MARKER` and an
+ * ed25519 signature of `get_record_signature_message`, either by the domain owner or, for a
+ * solana address, by that address as its right of association. This format is the crate's
+ * own, see `get_record_payload`: records written by other tools carry no signature.
+ */
+#[derive(Debug)]
+pub struct RecordV2 {
+    /// the record account and its payload.
+    pub entry: RecordEntry,
+    /// resolution of the domain or subdomain holding the record.
+    pub domain: DomainResolution,
+    /// whether the record was left by a previous owner.
+    pub staleness: Staleness,
+    /// signature following the payload, if any, or why it could not be read.
+    pub signature: TldParserResult<Option<Signature>>,
+}
+
+impl RecordV2 {
+    pub fn is_stale(&self) -> bool {
+        self.staleness.is_stale()
+    }
+
+    /// returns the record, or `StaleRecord` if it was left by a previous owner.
+    pub fn ensure_fresh(&self) -> TldParserResult<&RecordEntry> {
+        if self.is_stale() {
+            return Err(TldParserError::StaleRecord(self.entry.name_account));
+        }
+        Ok(&self.entry)
+    }
+
+    /// the message the embedded signature must sign, for the current domain owner.
+    pub fn signed_message(&self) -> TldParserResult<Vec<u8>> {
+        match &self.entry.value {
+            Ok(value) => Ok(get_record_signature_message(
+                &self.entry.name_account,
+                &self.domain.owner,
+                value,
+            )),
+            Err(error) => Err(TldParserError::InvalidRecordValue {
+                record: self.entry.record,
+                reason: error.to_string(),
+            }),
+        }
+    }
+
+    /// whether the embedded signature was made by `signer`.
+    pub fn verify_signature(&self, signer: &Pubkey) -> bool {
+        match (&self.signature, self.signed_message()) {
+            (Ok(Some(signature)), Ok(message)) => signature.verify(signer.as_ref(), &message),
+            _ => false,
+        }
+    }
+
+    /// whether the embedded signature was made by the current domain owner.
+    pub fn verify_owner_signature(&self) -> bool {
+        self.verify_signature(&self.domain.owner)
+    }
+
+    /// whether the embedded signature was made by the solana address stored in the record,
+    /// proving it agrees to be associated with the domain.
+    /// fails with `InvalidRecordValue` for records that do not hold a solana address.
+    pub fn verify_right_of_association(&self) -> TldParserResult<bool> {
+        match self.entry.parse_value()? {
+            RecordValue::Sol(address) | RecordValue::Shdw(address) => {
+                Ok(self.verify_signature(&address))
+            }
+            _ => Err(TldParserError::InvalidRecordValue {
+                record: self.entry.record,
+                reason: "not a solana address".to_string(),
+            }),
+        }
+    }
+}
+
+/// Returns the message signed for a record: its name account, the domain owner and the payload.
+/// binding the owner makes the signature void once the domain changes hands.
+pub fn get_record_signature_message(
+    record_account: &Pubkey,
+    domain_owner: &Pubkey,
+    value: &str,
+) -> Vec<u8> {
+    [
+        record_account.as_ref(),
+        domain_owner.as_ref(),
+        value.as_bytes(),
+    ]
+    .concat()
+}
+
+/// Returns the payload of a record: the u32 length prefixed value, followed by
+/// `NameRecordHeader::SIGNATURE_MARKER` and its signature if any, see `get_record_signature_message`.
+pub fn get_record_payload(value: &str, signature: Option<&Signature>) -> Vec<u8> {
+    let mut payload = (value.len() as u32).to_le_bytes().to_vec();
+    payload.extend_from_slice(value.as_bytes());
+    if let Some(signature) = signature {
+        payload.extend_from_slice(NameRecordHeader::SIGNATURE_MARKER);
+        payload.extend_from_slice(signature.as_ref());
+    }
+    payload
+}
@@ -0,0 +1,363 @@
+use {
+    crate::{
+        error::{TldParserError, TldParserResult},
+        types::Record,
+    },
+    base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine},
+    solana_sdk::{hash::hash, keccak, pubkey::Pubkey},
+    std::{fmt, str::FromStr},
+    url::Url,
+};
+
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32_CONST: u32 = 1;
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/**
+ * The payload of a record, parsed and validated for its `Record`.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RecordValue {
+    /// wallet of a `Record::SOL`.
+    Sol(Pubkey),
+    /// shadow drive storage account of a `Record::SHDW`.
+    Shdw(Pubkey),
+    /// address of a `Record::ETH`, EIP-55 checksummed.
+    Eth(String),
+    /// base58check or segwit address of a `Record::BTC`, segwit addresses are lowercased.
+    Btc(String),
+    /// base58check or segwit address of a `Record::LTC`, segwit addresses are lowercased.
+    Ltc(String),
+    /// base58check address of a `Record::DOGE`.
+    Doge(String),
+    /// CIDv0 or CIDv1 of a `Record::IPFS`, without its `ipfs://` scheme.
+    Ipfs(String),
+    /// transaction id of a `Record::ARWV`.
+    Arweave(String),
+    /// absolute url of a `Record::Url`.
+    Url(Url),
+    /// address of a `Record::Email`.
+    Email(String),
+    /// free form payload of the other records e.g. a twitter handle.
+    Text(String),
+}
+
+impl RecordValue {
+    /// Parses the payload of `record`, ignoring surrounding whitespace and nul padding.
+    /// Returns `InvalidRecordValue` if the payload is not a valid value for `record`.
+    pub fn parse(record: Record, value: &str) -> TldParserResult<Self> {
+        let value = value.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+        let parsed = if value.is_empty() {
+            Err("empty value")
+        } else {
+            match record {
+                Record::SOL => parse_pubkey(value).map(Self::Sol),
+                Record::SHDW => parse_pubkey(value).map(Self::Shdw),
+                Record::ETH => parse_eth_address(value).map(Self::Eth),
+                Record::BTC => parse_coin_address(value, Some("bc"), &[0x00, 0x05]).map(Self::Btc),
+                Record::LTC => {
+                    parse_coin_address(value, Some("ltc"), &[0x30, 0x32, 0x05]).map(Self::Ltc)
+                }
+                Record::DOGE => parse_coin_address(value, None, &[0x1e, 0x16]).map(Self::Doge),
+                Record::IPFS => parse_cid(value).map(Self::Ipfs),
+                Record::ARWV => parse_arweave_tx_id(value).map(Self::Arweave),
+                Record::Url => parse_url(value).map(Self::Url),
+                Record::Email => parse_email(value).map(Self::Email),
+                Record::LATTICA
+                | Record::Discord
+                | Record::Github
+                | Record::Reddit
+                | Record::Twitter
+                | Record::Telegram
+                | Record::Pic
+                | Record::POINT => Ok(Self::Text(value.to_string())),
+            }
+        };
+        parsed.map_err(|reason| TldParserError::InvalidRecordValue {
+            record,
+            reason: reason.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for RecordValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sol(pubkey) | Self::Shdw(pubkey) => write!(f, "{}", pubkey),
+            Self::Url(url) => write!(f, "{}", url),
+            Self::Eth(value)
+            | Self::Btc(value)
+            | Self::Ltc(value)
+            | Self::Doge(value)
+            | Self::Ipfs(value)
+            | Self::Arweave(value)
+            | Self::Email(value)
+            | Self::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+fn parse_pubkey(value: &str) -> Result<Pubkey, &'static str> {
+    Pubkey::from_str(value).map_err(|_| "not a base58 public key")
+}
+
+fn parse_eth_address(value: &str) -> Result<String, &'static str> {
+    let hex = value.strip_prefix("0x").ok_or("missing 0x prefix")?;
+    if hex.len() != 40 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err("not a hex encoded 20 byte address");
+    }
+    let checksummed = eip55_checksum(hex);
+    // single case addresses carry no checksum
+    let is_mixed_case =
+        hex.bytes().any(|b| b.is_ascii_lowercase()) && hex.bytes().any(|b| b.is_ascii_uppercase());
+    if is_mixed_case && checksummed[2..] != *hex {
+        return Err("invalid EIP-55 checksum");
+    }
+    Ok(checksummed)
+}
+
+fn eip55_checksum(hex: &str) -> String {
+    let hex = hex.to_ascii_lowercase();
+    let digest = keccak::hash(hex.as_bytes()).to_bytes();
+    let checksummed: String = hex
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = if i % 2 == 0 {
+                digest[i / 2] >> 4
+            } else {
+                digest[i / 2] & 0x0f
+            };
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!("0x{}", checksummed)
+}
+
+/// Parses a segwit address of `segwit_hrp` or a base58check address of one of `versions`.
+fn parse_coin_address(
+    value: &str,
+    segwit_hrp: Option<&str>,
+    versions: &[u8],
+) -> Result<String, &'static str> {
+    let lowercase = value.to_ascii_lowercase();
+    if let Some(hrp) = segwit_hrp.filter(|hrp| lowercase.starts_with(&format!("{}1", hrp))) {
+        decode_segwit_address(value, hrp)?;
+        return Ok(lowercase);
+    }
+    let (version, hash) = decode_base58check(value).ok_or("invalid base58check encoding")?;
+    if !versions.contains(&version) || hash.len() != 20 {
+        return Err("unknown address version");
+    }
+    Ok(value.to_string())
+}
+
+/// Decodes a base58check string into its version byte and payload.
+fn decode_base58check(value: &str) -> Option<(u8, Vec<u8>)> {
+    let data = bs58::decode(value).into_vec().ok()?;
+    if data.len() < 5 {
+        return None;
+    }
+    let (payload, checksum) = data.split_at(data.len() - 4);
+    let digest = hash(hash(payload).as_ref());
+    (digest.as_ref()[..4] == *checksum).then(|| (payload[0], payload[1..].to_vec()))
+}
+
+/// Decodes a BIP-173 or BIP-350 segwit address into its witness version and program.
+fn decode_segwit_address(value: &str, hrp: &str) -> Result<(u8, Vec<u8>), &'static str> {
+    let is_mixed_case = value.bytes().any(|b| b.is_ascii_lowercase())
+        && value.bytes().any(|b| b.is_ascii_uppercase());
+    if value.len() > 90 || is_mixed_case {
+        return Err("invalid bech32 encoding");
+    }
+    let value = value.to_ascii_lowercase();
+    let (value_hrp, data) = value.rsplit_once('1').ok_or("invalid bech32 encoding")?;
+    if value_hrp != hrp {
+        return Err("unknown address prefix");
+    }
+    let data = data
+        .bytes()
+        .map(|b| BECH32_CHARSET.iter().position(|c| *c == b).map(|p| p as u8))
+        .collect::<Option<Vec<u8>>>()
+        .filter(|data| data.len() > 6)
+        .ok_or("invalid bech32 encoding")?;
+    let checksum = bech32_polymod(
+        hrp.bytes()
+            .map(|b| b >> 5)
+            .chain([0])
+            .chain(hrp.bytes().map(|b| b & 0x1f))
+            .chain(data.iter().copied()),
+    );
+    let version = data[0];
+    let expected = if version == 0 {
+        BECH32_CONST
+    } else {
+        BECH32M_CONST
+    };
+    if checksum != expected {
+        return Err("invalid bech32 checksum");
+    }
+    let program = regroup_bits(&data[1..data.len() - 6]).ok_or("invalid witness program")?;
+    if version > 16
+        || !(2..=40).contains(&program.len())
+        || (version == 0 && program.len() != 20 && program.len() != 32)
+    {
+        return Err("invalid witness program");
+    }
+    Ok((version, program))
+}
+
+fn bech32_polymod(values: impl IntoIterator<Item = u8>) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    values.into_iter().fold(1, |checksum, value| {
+        let top = checksum >> 25;
+        GENERATOR.iter().enumerate().fold(
+            ((checksum & 0x1ffffff) << 5) ^ u32::from(value),
+            |checksum, (i, generator)| {
+                if (top >> i) & 1 == 1 {
+                    checksum ^ generator
+                } else {
+                    checksum
+                }
+            },
+        )
+    })
+}
+
+/// Regroups 5 bit words into bytes, rejecting incomplete or non zero padding.
+fn regroup_bits(words: &[u8]) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(words.len() * 5 / 8);
+    let mut acc = 0u32;
+    let mut bits = 0;
+    for word in words {
+        acc = ((acc << 5) | u32::from(*word)) & 0xfff;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((acc >> bits) as u8);
+        }
+    }
+    (bits < 5 && acc & ((1 << bits) - 1) == 0).then_some(bytes)
+}
+
+/// Parses a CIDv0, or a CIDv1 in base32, base58btc or base16 multibase.
+fn parse_cid(value: &str) -> Result<String, &'static str> {
+    let cid = value.strip_prefix("ipfs://").unwrap_or(value);
+    if cid.starts_with("Qm") {
+        let multihash = bs58::decode(cid).into_vec().map_err(|_| "invalid CIDv0")?;
+        if multihash.len() != 34 {
+            return Err("invalid CIDv0");
+        }
+        check_multihash(&multihash)?;
+        return Ok(cid.to_string());
+    }
+    let (multibase, encoded) = cid.split_at(cid.chars().next().map_or(0, char::len_utf8));
+    let bytes = match multibase {
+        "b" => decode_base32(encoded),
+        "B" if !encoded.bytes().any(|b| b.is_ascii_lowercase()) => {
+            decode_base32(&encoded.to_ascii_lowercase())
+        }
+        "z" => bs58::decode(encoded).into_vec().ok(),
+        "f" | "F" => decode_hex(encoded),
+        _ => None,
+    }
+    .ok_or("invalid CID encoding")?;
+    let (version, rest) = read_varint(&bytes).ok_or("invalid CID")?;
+    if version != 1 {
+        return Err("unsupported CID version");
+    }
+    let (_codec, multihash) = read_varint(rest).ok_or("invalid CID")?;
+    check_multihash(multihash)?;
+    Ok(cid.to_string())
+}
+
+fn check_multihash(multihash: &[u8]) -> Result<(), &'static str> {
+    let (_code, rest) = read_varint(multihash).ok_or("invalid multihash")?;
+    let (len, digest) = read_varint(rest).ok_or("invalid multihash")?;
+    if digest.is_empty() || digest.len() as u64 != len {
+        return Err("invalid multihash");
+    }
+    Ok(())
+}
+
+/// Reads an unsigned LEB128 varint and returns it with the remaining data.
+fn read_varint(data: &[u8]) -> Option<(u64, &[u8])> {
+    let mut value = 0u64;
+    for (i, byte) in data.iter().enumerate().take(9) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((value, &data[i + 1..]));
+        }
+    }
+    None
+}
+
+/// Decodes unpadded lowercase RFC 4648 base32.
+fn decode_base32(encoded: &str) -> Option<Vec<u8>> {
+    let words = encoded
+        .bytes()
+        .map(|b| {
+            BASE32_ALPHABET
+                .iter()
+                .position(|c| *c == b)
+                .map(|p| p as u8)
+        })
+        .collect::<Option<Vec<u8>>>()?;
+    regroup_bits(&words)
+}
+
+fn decode_hex(encoded: &str) -> Option<Vec<u8>> {
+    if !encoded.len().is_multiple_of(2) || !encoded.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..encoded.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&encoded[i..i + 2], 16).ok())
+        .collect()
+}
+
+fn parse_arweave_tx_id(value: &str) -> Result<String, &'static str> {
+    let is_tx_id =
+        value.len() == 43 && URL_SAFE_NO_PAD.decode(value).is_ok_and(|id| id.len() == 32);
+    if !is_tx_id {
+        return Err("not a base64url encoded 32 byte transaction id");
+    }
+    Ok(value.to_string())
+}
+
+fn parse_url(value: &str) -> Result<Url, &'static str> {
+    Url::parse(value)
+        .ok()
+        .filter(Url::has_host)
+        .ok_or("not an absolute url")
+}
+
+/// Checks the common ascii shape of an email address, not the full RFC 5322 grammar.
+fn parse_email(value: &str) -> Result<String, &'static str> {
+    let (local, domain) = value.rsplit_once('@').ok_or("missing @")?;
+    let is_valid_local = (1..=64).contains(&local.len())
+        && local
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+/=?^_`{|}~.-".contains(&b))
+        && !local.starts_with('.')
+        && !local.ends_with('.')
+        && !local.contains("..");
+    let is_valid_domain = domain.contains('.')
+        && domain.split('.').all(|label| {
+            (1..=63).contains(&label.len())
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        });
+    if !is_valid_local || !is_valid_domain {
+        return Err("not an email address");
+    }
+    Ok(value.to_string())
+}
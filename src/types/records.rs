@@ -1,6 +1,7 @@
 /**
  * List of ANS Records
  */
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub enum Record {
     IPFS,
     ARWV,
@@ -21,6 +22,30 @@ pub enum Record {
     SHDW,
     POINT,
 }
+impl Record {
+    /// every record, in declaration order.
+    pub const ALL: [Record; 18] = [
+        Record::IPFS,
+        Record::ARWV,
+        Record::SOL,
+        Record::ETH,
+        Record::BTC,
+        Record::LATTICA,
+        Record::LTC,
+        Record::DOGE,
+        Record::Email,
+        Record::Url,
+        Record::Discord,
+        Record::Github,
+        Record::Reddit,
+        Record::Twitter,
+        Record::Telegram,
+        Record::Pic,
+        Record::SHDW,
+        Record::POINT,
+    ];
+}
+
 /**
  * Retrieve the string version of the enum of ANS Records
  */
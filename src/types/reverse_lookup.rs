@@ -0,0 +1,72 @@
+use std::fmt;
+
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{constants::*, state::NameRecordHeader};
+
+/**
+ * Level of a name account below its tld.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum NameKind {
+    /// a domain e.g. "miester.abc"
+    Domain,
+    /// a subdomain e.g. "sub.miester.abc"
+    Subdomain,
+    /// a record of a domain or subdomain e.g. "url.miester.abc"
+    Record,
+}
+
+impl NameKind {
+    /// splits the prefix `get_domain_key` adds to the labels below a domain
+    /// e.g. "0sub" into the kind of account and the bare label.
+    pub fn from_prefixed_label(label: &str) -> (NameKind, &str) {
+        if let Some(label) = label.strip_prefix(RECORD_PREFIX) {
+            (NameKind::Record, label)
+        } else {
+            let label = label
+                .strip_prefix(SUBDOMAIN_PREFIX)
+                .or_else(|| label.strip_prefix(SUBDOMAIN_RECORD_PARENT_PREFIX))
+                .unwrap_or(label);
+            (NameKind::Subdomain, label)
+        }
+    }
+}
+
+/**
+ * Result of a reverse lookup: the domain name of a name account.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReverseLookup {
+    /// leftmost label without its tld or prefix e.g. "miester" or "sub" in "sub.miester.abc"
+    pub label: String,
+    /// labels between `label` and the tld, leftmost first e.g. ["miester"] in "sub.miester.abc"
+    pub parent_labels: Vec<String>,
+    /// tld including its leading dot e.g. ".abc"
+    pub tld: String,
+    /// whether the name account is a domain, a subdomain or a record.
+    pub kind: NameKind,
+    /// name account of the domain.
+    pub name_account: Pubkey,
+    /// parent name account, the tld's parent name account for a domain.
+    pub parent_account: Pubkey,
+    /// name record of the domain.
+    pub name_record: NameRecordHeader,
+}
+
+impl ReverseLookup {
+    /// fully qualified domain name e.g. "miester.abc" or "sub.miester.abc"
+    pub fn domain(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for ReverseLookup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label)?;
+        for parent_label in &self.parent_labels {
+            write!(f, ".{}", parent_label)?;
+        }
+        write!(f, "{}", self.tld)
+    }
+}
@@ -0,0 +1,32 @@
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{pda::*, utils::get_name_parent_from_tld};
+
+/**
+ * A tld with the accounts derived from it.
+ */
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TldInfo {
+    /// tld including its leading dot e.g. ".abc"
+    pub tld: String,
+    /// tld house of the tld, also the name class of its reverse lookups.
+    pub tld_house: Pubkey,
+    /// parent name account of the domains of the tld.
+    pub parent_name: Pubkey,
+    /// name house wrapping the domains of the tld.
+    pub name_house: Pubkey,
+}
+
+impl TldInfo {
+    /// derives the accounts of a tld e.g. ".abc"
+    pub fn new(tld: &str) -> Self {
+        let (tld_house, _) = find_tld_house(tld);
+        let (name_house, _) = find_name_house(&tld_house);
+        Self {
+            tld: tld.to_string(),
+            tld_house,
+            parent_name: get_name_parent_from_tld(tld),
+            name_house,
+        }
+    }
+}
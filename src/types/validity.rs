@@ -0,0 +1,40 @@
+/**
+ * Expiry status of a name record, see `NameRecordHeader::validity`.
+ *
+ * A record expires at `expires_at` but stays with its owner for a grace
+ * period during which it can still be renewed. It only becomes available
+ * to others once the grace period is over.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Validity {
+    /// the record has no expiry, `expires_at` is 0.
+    NonExpiring,
+    /// the record has not expired yet.
+    Active,
+    /// the record has expired but can still be renewed by its owner until `ends_at`.
+    InGracePeriod { ends_at: u64 },
+    /// the record has expired and its grace period is over.
+    Expired,
+}
+
+impl Validity {
+    /// computes the status of a record expiring at `expires_at` at the unix timestamp `now`.
+    pub fn from_expires_at(expires_at: u64, now: u64, grace_period: u64) -> Self {
+        if expires_at == 0 {
+            return Validity::NonExpiring;
+        }
+        let ends_at = expires_at.saturating_add(grace_period);
+        if now < expires_at {
+            Validity::Active
+        } else if now < ends_at {
+            Validity::InGracePeriod { ends_at }
+        } else {
+            Validity::Expired
+        }
+    }
+
+    /// whether the record still belongs to its owner, i.e. it has not fully expired.
+    pub fn is_valid(&self) -> bool {
+        *self != Validity::Expired
+    }
+}
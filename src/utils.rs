@@ -1,12 +1,17 @@
-use crate::{constants::*, name_record_handler::*, state::*, types::*};
+use crate::{
+    account_source::AccountSource, constants::*, error::*, name_record_handler::*,
+    normalize::canonicalize_label, pda::*, state::*, types::*,
+};
 use serde;
 use serde_json;
 use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_sdk::{hash::hashv, pubkey::Pubkey};
-use std::error::Error;
+use solana_sdk::{
+    hash::{hash, hashv},
+    pubkey::Pubkey,
+};
 
-pub fn get_name_parent_from_tld(tld: &String) -> Pubkey {
-    let parent_hashed_name = get_hashed_name(tld);
+pub fn get_name_parent_from_tld(tld: &str) -> Pubkey {
+    let parent_hashed_name = get_hashed_name(&canonicalize_label(tld));
     let [parent_hash_seed, parent_name_class_seed, parent_name_parent_seed] =
         get_name_service_seeds_from_hashed_name(parent_hashed_name, None, Some(&ORIGIN_TLD_KEY));
     let parent_seeds: &[&[u8]] = &[
@@ -34,18 +39,18 @@ pub fn get_name_service_seeds_from_hashed_name(
     ]
 }
 
-pub fn get_hashed_name(name: &String) -> Vec<u8> {
+pub fn get_hashed_name(name: &str) -> Vec<u8> {
     hashv(&[(NameRecordHeader::HASH_PREFIX.to_owned() + name).as_bytes()])
         .as_ref()
         .to_vec()
 }
 
 pub fn get_name_service_seeds_from_name(
-    name: &String,
+    name: &str,
     name_class_opt: Option<&Pubkey>,
     name_parent_opt: Option<&Pubkey>,
 ) -> [Vec<u8>; 3] {
-    let hashed_name: Vec<u8> = get_hashed_name(&name);
+    let hashed_name: Vec<u8> = get_hashed_name(name);
     let name_class = name_class_opt.cloned().unwrap_or_default();
     let name_parent = name_parent_opt.cloned().unwrap_or_default();
 
@@ -60,65 +65,113 @@ pub fn get_program_address(seeds_with_bump: &[&[u8]], program_id: &Pubkey) -> Pu
     Pubkey::create_program_address(seeds_with_bump, program_id).unwrap()
 }
 
-// not implemented yet
-pub async fn find_domain_name_records(
-    client: &RpcClient,
+/// Returns `src[start..end]`, or `AccountDataTooShort` if the data does not reach `end`.
+pub(crate) fn account_data_slice(src: &[u8], start: usize, end: usize) -> TldParserResult<&[u8]> {
+    src.get(start..end)
+        .ok_or(TldParserError::AccountDataTooShort {
+            expected: end,
+            actual: src.len(),
+        })
+}
+
+/// Returns `src[start..]`, or `AccountDataTooShort` if the data is shorter than `start`.
+pub(crate) fn account_data_from(src: &[u8], start: usize) -> TldParserResult<&[u8]> {
+    src.get(start..).ok_or(TldParserError::AccountDataTooShort {
+        expected: start,
+        actual: src.len(),
+    })
+}
+
+/// Reads a borsh `u32` length-prefixed string starting at `start`.
+pub(crate) fn read_length_prefixed_string(src: &[u8], start: usize) -> TldParserResult<&str> {
+    let len_end = start + 4;
+    let len_bytes = account_data_slice(src, start, len_end)?;
+    let len = u32::from_le_bytes([len_bytes[0], len_bytes[1], len_bytes[2], len_bytes[3]]) as usize;
+    let data = account_data_slice(src, len_end, len_end + len)?;
+    Ok(std::str::from_utf8(data)?)
+}
+
+/// Returns the anchor discriminator of an account type e.g. "TldHouse".
+pub fn get_account_discriminator(account_name: &str) -> [u8; 8] {
+    let mut discriminator = [0u8; 8];
+    discriminator
+        .copy_from_slice(&hash(format!("account:{}", account_name).as_bytes()).to_bytes()[..8]);
+    discriminator
+}
+
+/// Checks the anchor discriminator of `src` and returns the data following it.
+pub(crate) fn check_account_discriminator<'a>(
+    src: &'a [u8],
+    account_name: &'static str,
+) -> TldParserResult<&'a [u8]> {
+    if account_data_slice(src, 0, 8)? != get_account_discriminator(account_name) {
+        return Err(TldParserError::InvalidDiscriminator(account_name));
+    }
+    account_data_from(src, 8)
+}
+
+/// Returns the record accounts of a domain e.g. "miester.abc" that exist, for every `Record`,
+/// with their key and data, fetched in a single rpc call.
+pub async fn find_domain_name_records<S: AccountSource + ?Sized>(
+    client: &S,
     domain_tld: &str,
-) -> Result<Option<NameRecordHeader>, Box<dyn Error>> {
-    let multi_record_pubkeys = [
-        (get_domain_key(
-            &format!("{}.{}", get_record_string(Record::Url), domain_tld),
-            true,
-        ))?
-        .pubkey,
-        (get_domain_key(
-            &format!("{}.{}", get_record_string(Record::IPFS), domain_tld),
-            true,
-        ))?
-        .pubkey,
-        (get_domain_key(
-            &format!("{}.{}", get_record_string(Record::ARWV), domain_tld),
-            true,
-        ))?
-        .pubkey,
-        (get_domain_key(
-            &format!("{}.{}", get_record_string(Record::SHDW), domain_tld),
-            true,
-        ))?
-        .pubkey,
-    ];
+) -> TldParserResult<Vec<(Record, Pubkey, Vec<u8>)>> {
+    let multi_record_pubkeys = Record::ALL
+        .iter()
+        .map(|record| {
+            get_domain_key(
+                &format!("{}.{}", get_record_string(*record), domain_tld),
+                true,
+            )
+            .map(|domain_key| domain_key.pubkey)
+        })
+        .collect::<TldParserResult<Vec<Pubkey>>>()?;
 
     let name_record_account_infos = client
         .get_multiple_accounts(multi_record_pubkeys.as_ref())
         .await?;
 
-    for value in name_record_account_infos.into_iter().flatten() {
-        if let Ok(name_record_data) =
-            NameRecordHeader::deserialize_name_record(value.data.as_slice())
-        {
-            return Ok(Some(name_record_data));
-        }
-    }
-
-    Ok(None)
+    Ok(Record::ALL
+        .into_iter()
+        .zip(multi_record_pubkeys)
+        .zip(name_record_account_infos)
+        .filter_map(|((record, pubkey), account)| Some((record, pubkey, account?.data)))
+        .collect())
 }
-
-pub async fn get_record(
-    client: &RpcClient,
+pub async fn get_record<S: AccountSource + ?Sized>(
+    client: &S,
     domain_tld: &str,
     record: Record,
-) -> Result<Option<String>, Box<dyn Error>> {
+) -> TldParserResult<Option<String>> {
     let pubkey = (get_domain_key(
         &format!("{}.{}", get_record_string(record), domain_tld),
         true,
     ))?
     .pubkey;
     let name_record = client.get_account_data(&pubkey).await?;
-    let record_data = NameRecordHeader::deserialize_data_string(&name_record).unwrap();
+    let record_data = NameRecordHeader::deserialize_data_string(&name_record)?;
     Ok(Some(record_data))
 }
 
-#[derive(serde::Deserialize)]
+/// Returns a record of a domain e.g. "miester.abc", parsed and validated for `record`.
+/// Malformed payloads return `InvalidRecordValue` rather than the raw string.
+pub async fn get_record_value<S: AccountSource + ?Sized>(
+    client: &S,
+    domain_tld: &str,
+    record: Record,
+) -> TldParserResult<RecordValue> {
+    let record_data = get_record(client, domain_tld, record).await?;
+    RecordValue::parse(record, &record_data.unwrap_or_default())
+}
+
+/// Returns whether a domain is wrapped into an nft, i.e. its name record is owned by its nft record.
+pub fn is_wrapped_domain(name_account: &Pubkey, tld: &str, name_record_owner: &Pubkey) -> bool {
+    let (tld_house, _) = find_tld_house(tld);
+    let (name_house, _) = find_name_house(&tld_house);
+    *name_record_owner == find_nft_record(name_account, &name_house).0
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
 pub struct RpcTokenAccounts {
     pub address: String,
     pub amount: String,
@@ -128,11 +181,10 @@ pub struct RpcTokenAccounts {
 pub async fn get_token_largest_accounts(
     rpc: &RpcClient,
     mint_address: &Pubkey,
-) -> Result<solana_client::rpc_response::Response<Vec<RpcTokenAccounts>>, Box<dyn std::error::Error>>
-{
+) -> TldParserResult<solana_client::rpc_response::Response<Vec<RpcTokenAccounts>>> {
     let method = "getTokenLargestAccounts";
     let request = solana_client::rpc_request::RpcRequest::Custom { method };
     let params = serde_json::json!([mint_address.to_string()]);
 
-    rpc.send(request, params).await.map_err(|e| e.into())
+    Ok(rpc.send(request, params).await?)
 }
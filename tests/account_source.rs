@@ -0,0 +1,89 @@
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::{account::Account, program_option::COption, program_pack::Pack, pubkey::Pubkey};
+use spl_token_2022::state::{Account as TokenAccount, AccountState};
+use std::{error::Error, sync::Arc};
+use tldparser::*;
+
+fn account(owner: Pubkey, data: Vec<u8>) -> Account {
+    Account {
+        lamports: 1_000_000,
+        data,
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn token_account(mint: Pubkey, owner: Pubkey, amount: u64) -> Account {
+    let mut data = vec![0; TokenAccount::LEN];
+    TokenAccount {
+        mint,
+        owner,
+        amount,
+        delegate: COption::None,
+        state: AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    account(spl_token_2022::id(), data)
+}
+
+#[tokio::test]
+async fn memory_account_source() -> Result<(), Box<dyn Error>> {
+    let user = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let (user_domain, other_domain, holder, dust) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let mut user_data = vec![0; 200];
+    user_data[40..72].copy_from_slice(user.as_ref());
+
+    let source: MemoryAccountSource = [
+        (user_domain, account(ANS_PROGRAM_ID, user_data)),
+        (other_domain, account(ANS_PROGRAM_ID, vec![0; 200])),
+        (holder, token_account(mint, user, 1)),
+        (dust, token_account(mint, Pubkey::new_unique(), 0)),
+    ]
+    .into_iter()
+    .collect();
+
+    let missing = Pubkey::new_unique();
+    assert!(source.get_account(&missing).await?.is_none());
+    assert!(matches!(
+        source.get_account_data(&missing).await,
+        Err(TldParserError::AccountNotFound(key)) if key == missing
+    ));
+    let multiple = source
+        .get_multiple_accounts(&[user_domain, missing, other_domain])
+        .await?;
+    assert_eq!(
+        multiple.iter().map(Option::is_some).collect::<Vec<_>>(),
+        [true, false, true]
+    );
+
+    let memcmp = RpcFilterType::Memcmp(Memcmp::new_base58_encoded(40, user.as_ref()));
+    let filtered = source
+        .get_program_accounts(&ANS_PROGRAM_ID, vec![memcmp])
+        .await?;
+    assert_eq!(filtered.len(), 1);
+    assert_eq!(filtered[0].0, user_domain);
+    let by_size = source
+        .get_program_accounts(&ANS_PROGRAM_ID, vec![RpcFilterType::DataSize(200)])
+        .await?;
+    assert_eq!(by_size.len(), 2);
+
+    let largest = source.get_token_largest_accounts(&mint).await?;
+    assert_eq!(largest.len(), 2);
+    assert_eq!(largest[0].address, holder.to_string());
+    assert_eq!(largest[0].amount, "1");
+
+    // the parser runs on top of any account source
+    let parser = TldParser::new(Arc::new(source));
+    assert_eq!(parser.get_all_user_domains(&user).await?, vec![user_domain]);
+    Ok(())
+}
@@ -1,88 +1,328 @@
-use tldparser::*;
+mod common;
+
+use common::*;
+use tldparser::{name_record_handler::get_domain_key, *};
 use {
-    solana_client::nonblocking::rpc_client::RpcClient,
     solana_sdk::{pubkey, pubkey::Pubkey},
-    std::{error::Error, sync::Arc},
+    std::error::Error,
 };
 
 #[tokio::test]
 async fn all_lib_functions() -> Result<(), Box<dyn Error>> {
-    /// intializations and constants
-    const API_ENDPOINT: &str = "";
-
-    let rpc_client = RpcClient::new(API_ENDPOINT.to_string());
-    let parser = TldParser {
-        rpc_client: Arc::new(rpc_client),
-    };
+    // intializations and constants
+    let parser = fixture_parser(Some(FIXTURE_NOW))?;
     let owner: Pubkey = pubkey!("2EGGxj2qbNAJNgLCPKca8sxZYetyTjnoRspTPjzN2D67");
     let parent_account: Pubkey = pubkey!("3pSeaEVTcKLkXPCpZHDpHUMWAogYFZgKSiVtyvqcgo8a");
     let name_account: Pubkey = pubkey!("9YzfCEHb62bQ47snUyjkxhC9Eb6y7CSodK3m8CKWstjV");
     let abc = ".abc".to_string();
 
+    // miester.abc, onsol.bonk, expired.abc and the url and SOL records of miester.abc
     let result_all_user_domains = &parser.get_all_user_domains(&owner).await?;
-    // println!(
-    //     "result_all_user_domains: {:?} ",
-    //     result_all_user_domains.len()
-    // );
-    assert_eq!(result_all_user_domains.len(), 18);
-
-    let result_all_user_domains_from_tld =
-        parser.get_all_user_domains_from_tld(&owner, &abc).await?;
-    // println!(
-    //     "result_all_user_domains_from_tld: {:?} ",
-    //     result_all_user_domains_from_tld.len()
-    // );
-    assert_eq!(result_all_user_domains_from_tld.len(), 3);
+    assert_eq!(result_all_user_domains.len(), 5);
+
+    // miester.abc and expired.abc, cicu.abc is owned by its nft record
+    let result_all_user_domains_from_tld = parser
+        .get_all_user_domains_from_tld(&owner, &abc.parse()?)
+        .await?;
+    assert_eq!(result_all_user_domains_from_tld.len(), 2);
+    assert!(result_all_user_domains_from_tld.contains(&name_account));
+
+    // wrapped domain resolves to the holder of the nft
+    let result_owner_from_domain_tld = parser
+        .get_owner_from_domain_tld(&"cicu.abc".parse()?)
+        .await?;
+    assert_eq!(result_owner_from_domain_tld, owner);
 
     let result_owner_from_domain_tld = parser
-        .get_owner_from_domain_tld(&"cicu.abc".to_string())
+        .get_owner_from_domain_tld(&"miester.abc".parse()?)
         .await?;
-    // println!(
-    //     "result_owner_from_domain_tld: {:?} ",
-    //     result_owner_from_domain_tld
-    // );
     assert_eq!(result_owner_from_domain_tld, owner);
 
     let result_name_record_from_domain_tld = parser
-        .get_name_record_from_domain_tld(&"miester.abc".to_string())
+        .get_name_record_from_domain_tld(&"miester.abc".parse()?)
+        .await?;
+    assert_eq!(result_name_record_from_domain_tld.owner, owner);
+    assert_eq!(
+        result_name_record_from_domain_tld.parent_name,
+        parent_account
+    );
+
+    let result_name_record_from_name_account = parser
+        .get_name_record_from_name_account(&name_account)
         .await?;
-    // println!(
-    //     "result_name_record_from_domain_tld: {:?} ",
-    //     result_name_record_from_domain_tld
-    // );
-    assert!(result_name_record_from_domain_tld.is_valid);
+    assert_eq!(
+        result_name_record_from_name_account,
+        result_name_record_from_domain_tld
+    );
 
     let result_tld_from_parent_account =
         parser.get_tld_from_parent_account(&parent_account).await?;
-    // println!(
-    //     "result_tld_from_parent_account: {:?} ",
-    //     result_tld_from_parent_account
-    // );
     assert_eq!(result_tld_from_parent_account, abc);
 
+    let result_tld_house = parser.get_tld_house(&abc.parse()?).await?;
+    assert_eq!(result_tld_house.tld, abc);
+    assert_eq!(result_tld_house.treasury, find_tld_house_treasury(&abc).0);
+    let result_tld_house = parser.get_tld_house(&"onsol.bonk".parse()?).await?;
+    assert_eq!(result_tld_house.tld, ".bonk");
+
+    let result_all_tlds = parser.get_all_tlds().await?;
+    assert_eq!(
+        result_all_tlds
+            .iter()
+            .map(|tld| tld.tld.as_str())
+            .collect::<Vec<_>>(),
+        [".abc", ".bonk"]
+    );
+    assert_eq!(result_all_tlds[0].parent_name, parent_account);
+    assert_eq!(result_all_tlds[0].tld_house, find_tld_house(&abc).0);
+    assert_eq!(
+        result_all_tlds[0].name_house,
+        find_name_house(&result_all_tlds[0].tld_house).0
+    );
+
     // name_class or tld_house
     let (tld_house, _) = find_tld_house(&abc);
     let result_reverse_lookup_domain_name_with_known_name_class = parser
         .reverse_lookup_name_account_with_known_name_class(&name_account, &tld_house)
         .await?;
-    // println!(
-    //     "result_reverse_lookup_domain_name: {:?} ",
-    //     result_reverse_lookup_domain_name_with_known_name_class
-    // );
     assert_eq!(
         result_reverse_lookup_domain_name_with_known_name_class,
         "miester".to_string()
     );
 
     let result_main_domain = parser.get_main_domain(&owner).await?;
-    // println!("result_main_domain: {:?} ", result_main_domain);
+    assert_eq!(result_main_domain.name_account, name_account);
     assert_eq!(result_main_domain.tld, abc);
     assert_eq!(result_main_domain.domain, "miester".to_string());
 
     let result_reverse_lookup_domain_name =
         parser.reverse_lookup_name_account(&name_account).await?;
-    // println!("domain name: {:?} ", result_reverse_lookup_domain_name);
     assert_eq!(result_reverse_lookup_domain_name, "miester".to_string());
-    // assert!(false);
+
+    let result_reverse_lookup = parser.reverse_lookup(&name_account).await?;
+    assert_eq!(result_reverse_lookup.label, "miester");
+    assert_eq!(result_reverse_lookup.tld, abc);
+    assert_eq!(result_reverse_lookup.domain(), "miester.abc");
+    assert_eq!(result_reverse_lookup.to_string(), "miester.abc");
+    assert_eq!(result_reverse_lookup.name_account, name_account);
+    assert_eq!(result_reverse_lookup.parent_account, parent_account);
+    assert_eq!(
+        result_reverse_lookup.name_record,
+        result_name_record_from_name_account
+    );
+    assert_eq!(
+        parser
+            .reverse_lookup_with_known_name_class(&name_account, &tld_house)
+            .await?,
+        result_reverse_lookup
+    );
+    assert_eq!(
+        find_reverse_lookup(&name_account, &tld_house).0,
+        find_name_account_from_hashed_name(
+            &get_hashed_name(&name_account.to_string()),
+            Some(&tld_house),
+            None
+        )
+        .0
+    );
+
+    let onsol = get_name_parent_from_tld(".bonk");
+    let (onsol, _) = find_name_account_from_name("onsol", None, Some(&onsol));
+    assert_eq!(parser.reverse_lookup_name_account(&onsol).await?, "onsol");
+
+    let result_url_record =
+        get_record(parser.account_source.as_ref(), "miester.abc", Record::Url).await?;
+    assert_eq!(result_url_record, Some("https://alldomains.id".to_string()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn failure_kinds() -> Result<(), Box<dyn Error>> {
+    let parser = fixture_parser(Some(FIXTURE_NOW))?;
+
+    let result_expired = parser
+        .get_owner_from_domain_tld(&"expired.abc".parse()?)
+        .await;
+    assert!(matches!(
+        result_expired,
+        Err(TldParserError::Expired {
+            expires_at: 1_600_000_000,
+            ..
+        })
+    ));
+
+    let result_expired = parser
+        .get_name_record_from_domain_tld(&"expired.abc".parse()?)
+        .await?;
+    assert!(!result_expired.is_valid);
+    assert_eq!(parser.validity(&result_expired).await?, Validity::Expired);
+
+    // a grace period longer than the time since expiry keeps the owner
+    let lenient_parser = fixture_parser(Some(FIXTURE_NOW))?.with_grace_period(u64::MAX);
+    let result_in_grace_period = lenient_parser
+        .get_owner_from_domain_tld(&"expired.abc".parse()?)
+        .await?;
+    assert_eq!(
+        result_in_grace_period,
+        pubkey!("2EGGxj2qbNAJNgLCPKca8sxZYetyTjnoRspTPjzN2D67")
+    );
+    let result_in_grace_period = lenient_parser
+        .get_name_record_from_domain_tld(&"expired.abc".parse()?)
+        .await?;
+    assert!(result_in_grace_period.is_valid);
+    assert_eq!(
+        lenient_parser.validity(&result_in_grace_period).await?,
+        Validity::InGracePeriod { ends_at: u64::MAX }
+    );
+
+    let result_missing = parser
+        .get_owner_from_domain_tld(&"missing.abc".parse()?)
+        .await;
+    assert!(matches!(
+        result_missing,
+        Err(TldParserError::AccountNotFound(_))
+    ));
+
+    // reverse lookup under the wrong tld house
+    let (bonk_tld_house, _) = find_tld_house(".bonk");
+    let result_wrong_name_class = parser
+        .reverse_lookup_with_known_name_class(
+            &pubkey!("9YzfCEHb62bQ47snUyjkxhC9Eb6y7CSodK3m8CKWstjV"),
+            &bonk_tld_house,
+        )
+        .await;
+    assert!(matches!(
+        result_wrong_name_class,
+        Err(TldParserError::AccountNotFound(key))
+            if key == find_reverse_lookup(
+                &pubkey!("9YzfCEHb62bQ47snUyjkxhC9Eb6y7CSodK3m8CKWstjV"),
+                &bonk_tld_house
+            ).0
+    ));
+
+    let result_no_main_domain = parser.get_main_domain(&Pubkey::new_unique()).await;
+    assert!(matches!(
+        result_no_main_domain,
+        Err(TldParserError::AccountNotFound(_))
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn reverse_lookup_reads_only_the_tld() -> Result<(), Box<dyn Error>> {
+    // a tld house whose fields after the tld differ from `TldHouse`
+    let mut account_source = fixture_accounts()?;
+    let (tld_house, _) = find_tld_house(".abc");
+    let mut account = account_source.get(&tld_house).unwrap().clone();
+    account
+        .data
+        .truncate(TldHouse::TLD_OFFSET + 4 + ".abc".len());
+    account_source.insert(tld_house, account);
+    let parser = parser_over(account_source, Some(FIXTURE_NOW));
+
+    let name_account = pubkey!("9YzfCEHb62bQ47snUyjkxhC9Eb6y7CSodK3m8CKWstjV");
+    assert_eq!(
+        parser.reverse_lookup(&name_account).await?.domain(),
+        "miester.abc"
+    );
+    assert_eq!(
+        parser
+            .reverse_lookup_with_known_name_class(&name_account, &tld_house)
+            .await?
+            .tld,
+        ".abc"
+    );
+    assert_eq!(
+        parser.reverse_lookup_many(&[name_account]).await?[&name_account],
+        "miester.abc"
+    );
+    assert_eq!(
+        parser
+            .get_tld_from_parent_account(&pubkey!("3pSeaEVTcKLkXPCpZHDpHUMWAogYFZgKSiVtyvqcgo8a"))
+            .await?,
+        ".abc"
+    );
+    // the tld house decodes no field after the tld either
+    assert_eq!(parser.get_tld_house(&".abc".parse()?).await?.tld, ".abc");
+    Ok(())
+}
+
+#[tokio::test]
+async fn reverse_lookup_stops_at_the_tld_house() -> Result<(), Box<dyn Error>> {
+    // the tld parent name account is found by its tld house owner, whatever its own parent
+    let mut account_source = fixture_accounts()?;
+    let parent_account = pubkey!("3pSeaEVTcKLkXPCpZHDpHUMWAogYFZgKSiVtyvqcgo8a");
+    let mut account = account_source.get(&parent_account).unwrap().clone();
+    account.data[8..40].copy_from_slice(Pubkey::new_unique().as_ref());
+    account_source.insert(parent_account, account);
+    let parser = parser_over(account_source, Some(FIXTURE_NOW));
+
+    let sub = get_domain_key("sub.miester.abc", false)?.pubkey;
+    assert_eq!(
+        parser.reverse_lookup(&sub).await?.domain(),
+        "sub.miester.abc"
+    );
+
+    // without its tld house the walk goes past the tld parent name account
+    let mut account_source = fixture_accounts()?;
+    account_source.remove(&find_tld_house(".abc").0);
+    let parser = parser_over(account_source, Some(FIXTURE_NOW));
+    assert!(matches!(
+        parser.reverse_lookup(&sub).await,
+        Err(TldParserError::AccountNotFound(key)) if key == ORIGIN_TLD_KEY
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn reverse_lookup_name_account_reads_only_the_reverse_lookup() -> Result<(), Box<dyn Error>> {
+    // neither the name account nor the tld house is needed for the label
+    let mut account_source = fixture_accounts()?;
+    let name_account = pubkey!("9YzfCEHb62bQ47snUyjkxhC9Eb6y7CSodK3m8CKWstjV");
+    let (tld_house, _) = find_tld_house(".abc");
+    account_source.remove(&name_account);
+    account_source.remove(&tld_house);
+    let parser = parser_over(account_source, Some(FIXTURE_NOW));
+
+    assert_eq!(
+        parser
+            .reverse_lookup_name_account_with_known_name_class(&name_account, &tld_house)
+            .await?,
+        "miester"
+    );
+    assert!(matches!(
+        parser
+            .reverse_lookup_with_known_name_class(&name_account, &tld_house)
+            .await,
+        Err(TldParserError::AccountNotFound(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn fixture_loader_accepts_rpc_output() -> Result<(), Box<dyn Error>> {
+    let pubkey = Pubkey::new_unique();
+    let response = r#"{"jsonrpc":"2.0","result":{"context":{"slot":1},"value":{"data":["AQID","base64"],"executable":false,"lamports":1,"owner":"11111111111111111111111111111111","rentEpoch":0,"space":3}},"id":1}"#;
+    let value = r#"{"data":["BAU=","base64"],"executable":false,"lamports":1,"owner":"11111111111111111111111111111111","rentEpoch":0}"#;
+    let missing = r#"{"jsonrpc":"2.0","result":{"context":{"slot":1},"value":null},"id":1}"#;
+    let other = Pubkey::new_unique();
+
+    let mut account_source = MemoryAccountSource::new();
+    account_source.load_fixture_json(&format!(
+        r#"{{"{}": {}, "{}": {}, "{}": {}}}"#,
+        pubkey,
+        response,
+        other,
+        value,
+        Pubkey::new_unique(),
+        missing
+    ))?;
+    assert_eq!(account_source.len(), 2);
+    assert_eq!(account_source.get(&pubkey).unwrap().data, vec![1, 2, 3]);
+    assert_eq!(account_source.get(&other).unwrap().data, vec![4, 5]);
+
+    assert!(account_source
+        .load_fixture_json(r#"{"not a pubkey": {}}"#)
+        .is_err());
     Ok(())
 }
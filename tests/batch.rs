@@ -0,0 +1,191 @@
+mod common;
+
+use {
+    async_trait::async_trait,
+    common::*,
+    solana_client::rpc_filter::RpcFilterType,
+    solana_sdk::{account::Account, pubkey, pubkey::Pubkey},
+    std::{error::Error, sync::Mutex},
+    tldparser::{name_record_handler::get_domain_key, *},
+};
+
+const OWNER: Pubkey = pubkey!("2EGGxj2qbNAJNgLCPKca8sxZYetyTjnoRspTPjzN2D67");
+const AUTHORITY: Pubkey = pubkey!("8XVnbeamKFPJGq1QdrMXq7ndvhiP8Dgm1HEvnLpAGP3e");
+
+/// fixture accounts, recording the size of every `get_multiple_accounts` request.
+#[derive(Default)]
+struct CountingAccountSource {
+    accounts: MemoryAccountSource,
+    multiple_accounts_requests: Mutex<Vec<usize>>,
+}
+
+#[async_trait]
+impl AccountSource for CountingAccountSource {
+    async fn get_account(&self, pubkey: &Pubkey) -> TldParserResult<Option<Account>> {
+        self.accounts.get_account(pubkey).await
+    }
+
+    async fn get_multiple_accounts(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> TldParserResult<Vec<Option<Account>>> {
+        self.multiple_accounts_requests
+            .lock()
+            .unwrap()
+            .push(pubkeys.len());
+        self.accounts.get_multiple_accounts(pubkeys).await
+    }
+
+    async fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        filters: Vec<RpcFilterType>,
+    ) -> TldParserResult<Vec<(Pubkey, Account)>> {
+        self.accounts
+            .get_program_accounts(program_id, filters)
+            .await
+    }
+
+    async fn get_token_largest_accounts(
+        &self,
+        mint: &Pubkey,
+    ) -> TldParserResult<Vec<RpcTokenAccounts>> {
+        self.accounts.get_token_largest_accounts(mint).await
+    }
+}
+
+fn fixture_parser() -> Result<TldParser<CountingAccountSource>, Box<dyn Error>> {
+    let account_source = CountingAccountSource {
+        accounts: fixture_accounts()?,
+        ..Default::default()
+    };
+    Ok(parser_over(account_source, Some(FIXTURE_NOW)))
+}
+
+#[tokio::test]
+async fn resolve_owners() -> Result<(), Box<dyn Error>> {
+    let parser = fixture_parser()?;
+    let domains: Vec<DomainName> = [
+        "miester.abc",
+        "cicu.abc",
+        "expired.abc",
+        "missing.abc",
+        "sub.miester.abc",
+        "onsol.bonk",
+        "old.expired.abc",
+        "url.sub.miester.abc",
+    ]
+    .iter()
+    .map(|domain| domain.parse())
+    .collect::<Result<_, _>>()?;
+    let owners = parser.resolve_owners(&domains).await?;
+    assert_eq!(owners.len(), domains.len());
+    assert_eq!(owners[0].as_ref().ok(), Some(&OWNER));
+    assert_eq!(owners[1].as_ref().ok(), Some(&OWNER));
+    assert!(matches!(owners[2], Err(TldParserError::Expired { .. })));
+    assert!(matches!(owners[3], Err(TldParserError::AccountNotFound(_))));
+    assert_eq!(owners[4].as_ref().ok(), Some(&AUTHORITY));
+    assert_eq!(owners[5].as_ref().ok(), Some(&OWNER));
+    // the subdomain itself never expires, its domain has
+    let expired = get_domain_key("expired.abc", false)?.pubkey;
+    assert!(matches!(
+        owners[6],
+        Err(TldParserError::Expired { name_account, .. }) if name_account == expired
+    ));
+    assert!(matches!(owners[7], Err(TldParserError::InvalidDomain(_))));
+
+    // same answers as one by one
+    for (domain, owner) in domains.iter().zip(&owners) {
+        let single = parser.get_owner_from_domain_tld(domain).await;
+        assert_eq!(single.ok(), owner.as_ref().ok().copied(), "{}", domain);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn resolve_owners_isolates_malformed_accounts() -> Result<(), Box<dyn Error>> {
+    // miester.abc is truncated in the middle of its header
+    let miester = get_domain_key("miester.abc", false)?.pubkey;
+    let mut accounts = fixture_accounts()?;
+    let mut account = accounts.get(&miester).unwrap().clone();
+    account.data.truncate(100);
+    accounts.insert(miester, account);
+    let parser = parser_over(accounts, Some(FIXTURE_NOW));
+
+    let domains: Vec<DomainName> = ["cicu.abc", "miester.abc", "onsol.bonk", "sub.miester.abc"]
+        .iter()
+        .map(|domain| domain.parse())
+        .collect::<Result<_, _>>()?;
+    let owners = parser.resolve_owners(&domains).await?;
+    assert_eq!(owners[0].as_ref().ok(), Some(&OWNER));
+    assert!(matches!(owners[1], Err(TldParserError::Deserialization(_))));
+    assert_eq!(owners[2].as_ref().ok(), Some(&OWNER));
+    // the subdomain fails with its parent
+    assert!(matches!(owners[3], Err(TldParserError::Deserialization(_))));
+    Ok(())
+}
+
+#[tokio::test]
+async fn resolve_owners_is_chunked() -> Result<(), Box<dyn Error>> {
+    let parser = fixture_parser()?;
+    let mut domains: Vec<DomainName> = (0..250)
+        .map(|i| format!("unknown{}.abc", i).parse())
+        .collect::<Result<_, _>>()?;
+    domains.push("cicu.abc".parse()?);
+    let owners = parser.resolve_owners(&domains).await?;
+    assert_eq!(owners.len(), 251);
+    assert!(owners[..250]
+        .iter()
+        .all(|owner| matches!(owner, Err(TldParserError::AccountNotFound(_)))));
+    assert_eq!(owners[250].as_ref().ok(), Some(&OWNER));
+
+    // three chunks of name accounts, then the nft record and the token account
+    assert_eq!(
+        *parser
+            .account_source
+            .multiple_accounts_requests
+            .lock()
+            .unwrap(),
+        [MAX_MULTIPLE_ACCOUNTS, MAX_MULTIPLE_ACCOUNTS, 51, 1, 1]
+    );
+    assert!(parser.resolve_owners(&[]).await?.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn reverse_lookup_many() -> Result<(), Box<dyn Error>> {
+    let parser = fixture_parser()?;
+    let name_accounts: Vec<Pubkey> = ["miester.abc", "cicu.abc", "onsol.bonk", "expired.abc"]
+        .iter()
+        .map(|domain| get_domain_key(domain, false).map(|key| key.pubkey))
+        .collect::<Result<_, _>>()?;
+    let missing = Pubkey::new_unique();
+    let mut lookups = name_accounts.clone();
+    lookups.push(missing);
+    // duplicates and non name accounts are fine
+    lookups.push(name_accounts[0]);
+    lookups.push(find_tld_house(".abc").0);
+
+    let domains = parser.reverse_lookup_many(&lookups).await?;
+    assert_eq!(domains.len(), 4);
+    assert_eq!(domains[&name_accounts[0]], "miester.abc");
+    assert_eq!(domains[&name_accounts[1]], "cicu.abc");
+    assert_eq!(domains[&name_accounts[2]], "onsol.bonk");
+    assert_eq!(domains[&name_accounts[3]], "expired.abc");
+    assert!(!domains.contains_key(&missing));
+
+    // name records, the two parents, their tld houses and the reverse lookups
+    assert_eq!(
+        *parser
+            .account_source
+            .multiple_accounts_requests
+            .lock()
+            .unwrap(),
+        [lookups.len(), 2, 2, 5]
+    );
+    for name_account in &name_accounts {
+        let label = parser.reverse_lookup_name_account(name_account).await?;
+        assert!(domains[name_account].starts_with(&label));
+    }
+    Ok(())
+}
@@ -0,0 +1,39 @@
+//! fixture factory shared by the integration tests.
+#![allow(dead_code)]
+
+use {
+    std::{error::Error, sync::Arc},
+    tldparser::*,
+};
+
+/// account snapshots in getAccountInfo format, see tests/fixtures/README.md
+pub const FIXTURES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+/// a time after every fixture domain was registered, at which expired.abc is past its grace period.
+pub const FIXTURE_NOW: FixedTimeSource = FixedTimeSource(1_700_000_000);
+
+/// every account of the fixture directory.
+pub fn fixture_accounts() -> Result<MemoryAccountSource, Box<dyn Error>> {
+    let mut account_source = MemoryAccountSource::new();
+    account_source.load_fixture_dir(FIXTURES_DIR)?;
+    Ok(account_source)
+}
+
+/// a parser over the fixture accounts, on the system clock unless a time source is given.
+pub fn fixture_parser(
+    time_source: Option<FixedTimeSource>,
+) -> Result<TldParser<MemoryAccountSource>, Box<dyn Error>> {
+    Ok(parser_over(fixture_accounts()?, time_source))
+}
+
+/// a parser over any account source, on the system clock unless a time source is given.
+pub fn parser_over<S: AccountSource>(
+    account_source: S,
+    time_source: Option<FixedTimeSource>,
+) -> TldParser<S> {
+    let parser = TldParser::new(Arc::new(account_source));
+    match time_source {
+        Some(time_source) => parser.with_time_source(time_source),
+        None => parser,
+    }
+}
@@ -0,0 +1,319 @@
+use tldparser::{name_record_handler::get_domain_key, *};
+use {
+    solana_sdk::{pubkey, pubkey::Pubkey},
+    std::error::Error,
+};
+
+const OWNER: Pubkey = pubkey!("2EGGxj2qbNAJNgLCPKca8sxZYetyTjnoRspTPjzN2D67");
+const PARENT: Pubkey = pubkey!("3pSeaEVTcKLkXPCpZHDpHUMWAogYFZgKSiVtyvqcgo8a");
+const NAME_ACCOUNT: Pubkey = pubkey!("9YzfCEHb62bQ47snUyjkxhC9Eb6y7CSodK3m8CKWstjV");
+const INVALID_UTF8: &[u8] = &[0xf0, 0x28, 0x8c, 0x28];
+
+fn borsh_string(value: &[u8]) -> Vec<u8> {
+    let mut data = (value.len() as u32).to_le_bytes().to_vec();
+    data.extend_from_slice(value);
+    data
+}
+
+fn name_record_header_data(expires_at: u64) -> Vec<u8> {
+    let mut data = vec![0; 8];
+    data.extend_from_slice(PARENT.as_ref());
+    data.extend_from_slice(OWNER.as_ref());
+    data.extend_from_slice(Pubkey::default().as_ref());
+    data.extend_from_slice(&expires_at.to_le_bytes());
+    data.extend_from_slice(&1_000u64.to_le_bytes());
+    data.extend_from_slice(&[0, 0]);
+    data.resize(NameRecordHeader::LEN, 0);
+    data
+}
+
+fn main_domain_data(domain: &[u8]) -> Vec<u8> {
+    let mut data = vec![0; 8];
+    data.extend_from_slice(NAME_ACCOUNT.as_ref());
+    data.extend(borsh_string(b".abc"));
+    data.extend(borsh_string(domain));
+    data
+}
+
+fn nft_record_data() -> Vec<u8> {
+    let mut data = vec![0; 8];
+    data.extend_from_slice(&[1, 255]);
+    data.extend_from_slice(NAME_ACCOUNT.as_ref());
+    data.extend_from_slice(OWNER.as_ref());
+    data.extend_from_slice(PARENT.as_ref());
+    data.extend_from_slice(Pubkey::default().as_ref());
+    data
+}
+
+fn tld_house_data(tld: &[u8]) -> Vec<u8> {
+    let mut data = get_account_discriminator("TldHouse").to_vec();
+    data.extend_from_slice(OWNER.as_ref());
+    data.extend_from_slice(PARENT.as_ref());
+    data.extend_from_slice(OWNER.as_ref());
+    data.extend(borsh_string(tld));
+    data
+}
+
+#[test]
+fn name_record_header() -> Result<(), Box<dyn Error>> {
+    let data = name_record_header_data(42);
+    let header = NameRecordHeader::deserialize_name_record(&data)?;
+    assert_eq!(header.parent_name, PARENT);
+    assert_eq!(header.owner, OWNER);
+    assert_eq!(header.expires_at, 42);
+
+    // oversized: trailing record data is ignored
+    let mut oversized = data.clone();
+    oversized.extend_from_slice(&[7; 64]);
+    assert_eq!(
+        NameRecordHeader::deserialize_name_record(&oversized)?,
+        header
+    );
+
+    // truncated
+    assert!(matches!(
+        NameRecordHeader::deserialize_name_record(&[]),
+        Err(TldParserError::AccountDataTooShort {
+            expected: 8,
+            actual: 0
+        })
+    ));
+    assert!(matches!(
+        NameRecordHeader::deserialize_name_record(&data[..7]),
+        Err(TldParserError::AccountDataTooShort { .. })
+    ));
+    assert!(matches!(
+        NameRecordHeader::deserialize_name_record(&data[..50]),
+        Err(TldParserError::Deserialization(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn data_string() -> Result<(), Box<dyn Error>> {
+    let mut data = name_record_header_data(0);
+    data.extend(borsh_string(b"https://alldomains.id"));
+    assert_eq!(
+        NameRecordHeader::deserialize_data_string(&data)?,
+        "https://alldomains.id"
+    );
+
+    // oversized: bytes after the string are ignored
+    let mut oversized = data.clone();
+    oversized.extend_from_slice(&[0; 32]);
+    assert_eq!(
+        NameRecordHeader::deserialize_data_string(&oversized)?,
+        "https://alldomains.id"
+    );
+
+    // truncated length prefix and truncated string
+    let header_only = name_record_header_data(0);
+    assert!(matches!(
+        NameRecordHeader::deserialize_data_string(&header_only),
+        Err(TldParserError::AccountDataTooShort { .. })
+    ));
+    assert!(matches!(
+        NameRecordHeader::deserialize_data_string(&data[..data.len() - 1]),
+        Err(TldParserError::AccountDataTooShort { .. })
+    ));
+
+    // length prefix larger than the account
+    let mut bad_len = name_record_header_data(0);
+    bad_len.extend_from_slice(&u32::MAX.to_le_bytes());
+    assert!(matches!(
+        NameRecordHeader::deserialize_data_string(&bad_len),
+        Err(TldParserError::AccountDataTooShort { .. })
+    ));
+
+    // non utf-8
+    let mut non_utf8 = name_record_header_data(0);
+    non_utf8.extend(borsh_string(INVALID_UTF8));
+    assert!(matches!(
+        NameRecordHeader::deserialize_data_string(&non_utf8),
+        Err(TldParserError::InvalidUtf8(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn reverse_lookup_domain_name() -> Result<(), Box<dyn Error>> {
+    let mut data = name_record_header_data(0);
+    data.extend_from_slice(b"miester");
+    assert_eq!(
+        NameRecordHeader::deserialize_reverse_lookup_domain_name(&data)?,
+        "miester"
+    );
+
+    // oversized: zero padding is trimmed
+    let mut oversized = data.clone();
+    oversized.extend_from_slice(&[0; 16]);
+    assert_eq!(
+        NameRecordHeader::deserialize_reverse_lookup_domain_name(&oversized)?,
+        "miester"
+    );
+
+    // truncated
+    assert!(matches!(
+        NameRecordHeader::deserialize_reverse_lookup_domain_name(&data[..100]),
+        Err(TldParserError::AccountDataTooShort { .. })
+    ));
+
+    // non utf-8
+    let mut non_utf8 = name_record_header_data(0);
+    non_utf8.extend_from_slice(INVALID_UTF8);
+    assert!(matches!(
+        NameRecordHeader::deserialize_reverse_lookup_domain_name(&non_utf8),
+        Err(TldParserError::InvalidUtf8(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn main_domain() -> Result<(), Box<dyn Error>> {
+    let data = main_domain_data(b"miester");
+    let main_domain = MainDomain::deserialize_main_domain(&data)?;
+    assert_eq!(main_domain.name_account, NAME_ACCOUNT);
+    assert_eq!(main_domain.tld, ".abc");
+    assert_eq!(main_domain.domain, "miester");
+
+    // oversized: unused space in the account is ignored
+    let mut oversized = data.clone();
+    oversized.resize(MainDomain::MAIN_DOMAIN_SIZE, 0);
+    assert_eq!(
+        MainDomain::deserialize_main_domain(&oversized)?,
+        main_domain
+    );
+
+    // truncated
+    assert!(matches!(
+        MainDomain::deserialize_main_domain(&data[..4]),
+        Err(TldParserError::AccountDataTooShort { .. })
+    ));
+    assert!(matches!(
+        MainDomain::deserialize_main_domain(&data[..data.len() - 1]),
+        Err(TldParserError::Deserialization(_))
+    ));
+
+    // non utf-8
+    assert!(matches!(
+        MainDomain::deserialize_main_domain(&main_domain_data(INVALID_UTF8)),
+        Err(TldParserError::Deserialization(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn nft_record() -> Result<(), Box<dyn Error>> {
+    let data = nft_record_data();
+    let nft_record = NftRecord::from_account_info(&data)?;
+    assert!(nft_record.is_active());
+    assert_eq!(nft_record.name_account, NAME_ACCOUNT);
+    assert_eq!(nft_record.nft_mint_account, PARENT);
+
+    // oversized: reserved space is ignored
+    let mut oversized = data.clone();
+    oversized.resize(NftRecord::LEN, 0);
+    assert_eq!(NftRecord::from_account_info(&oversized)?, nft_record);
+
+    // truncated
+    assert!(matches!(
+        NftRecord::from_account_info(&data[..3]),
+        Err(TldParserError::AccountDataTooShort { .. })
+    ));
+    assert!(matches!(
+        NftRecord::from_account_info(&data[..data.len() - 1]),
+        Err(TldParserError::Deserialization(_))
+    ));
+
+    // unknown tag
+    let mut bad_tag = data.clone();
+    bad_tag[8] = 9;
+    assert!(matches!(
+        NftRecord::from_account_info(&bad_tag),
+        Err(TldParserError::Deserialization(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn tld_house() -> Result<(), Box<dyn Error>> {
+    let data = tld_house_data(b".abc");
+    let tld_house = TldHouse::deserialize_tld_house(&data)?;
+    assert_eq!(tld_house.authority, OWNER);
+    assert_eq!(tld_house.treasury, PARENT);
+    assert_eq!(tld_house.tld, ".abc");
+
+    // the configuration after the tld is ignored
+    let mut configured = data.clone();
+    configured.extend_from_slice(&[254, 253, 1, 2, 3]);
+    assert_eq!(TldHouse::deserialize_tld_house(&configured)?, tld_house);
+
+    // oversized: zero padding inside the string and after the account is ignored
+    let mut oversized = tld_house_data(b".abc\0\0\0\0");
+    oversized.resize(oversized.len() + 64, 0);
+    assert_eq!(TldHouse::deserialize_tld_house(&oversized)?, tld_house);
+
+    // truncated
+    assert!(matches!(
+        TldHouse::deserialize_tld_house(&data[..6]),
+        Err(TldParserError::AccountDataTooShort { .. })
+    ));
+    assert!(matches!(
+        TldHouse::deserialize_tld_house(&data[..data.len() - 1]),
+        Err(TldParserError::Deserialization(_))
+    ));
+
+    // another account type
+    let mut other_account = data.clone();
+    other_account[..8].copy_from_slice(&get_account_discriminator("MainDomain"));
+    assert!(matches!(
+        TldHouse::deserialize_tld_house(&other_account),
+        Err(TldParserError::InvalidDiscriminator("TldHouse"))
+    ));
+
+    // non utf-8
+    assert!(matches!(
+        TldHouse::deserialize_tld_house(&tld_house_data(INVALID_UTF8)),
+        Err(TldParserError::Deserialization(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn tld_house_tld_only() -> Result<(), Box<dyn Error>> {
+    // the tld is read whatever the discriminator and the fields after it
+    let mut data = tld_house_data(b".abc\0\0");
+    data[..8].copy_from_slice(&[0; 8]);
+    data.truncate(TldHouse::TLD_OFFSET + 4 + 6);
+    assert_eq!(TldHouse::deserialize_tld(&data)?, ".abc");
+    assert!(TldHouse::deserialize_tld_house(&data).is_err());
+
+    assert!(matches!(
+        TldHouse::deserialize_tld(&data[..TldHouse::TLD_OFFSET + 6]),
+        Err(TldParserError::AccountDataTooShort { .. })
+    ));
+    assert!(matches!(
+        TldHouse::deserialize_tld(&tld_house_data(INVALID_UTF8)),
+        Err(TldParserError::InvalidUtf8(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn domain_key() -> Result<(), Box<dyn Error>> {
+    assert!(!get_domain_key("miester.abc", false)?.is_sub);
+    assert!(get_domain_key("sub.miester.abc", false)?.is_sub);
+    assert!(get_domain_key("sol.sub.miester.abc", true)?.is_sub_record);
+
+    for invalid in ["miester", "sol.sub.miester.abc", "a.b.c.d.abc"] {
+        assert!(matches!(
+            get_domain_key(invalid, false),
+            Err(TldParserError::InvalidDomain(_))
+        ));
+    }
+    assert!(matches!(
+        get_domain_key("a.b.c.d.abc", true),
+        Err(TldParserError::InvalidDomain(_))
+    ));
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use std::error::Error;
+use tldparser::{name_record_handler::get_domain_key, *};
+
+#[test]
+fn parses_every_level() -> Result<(), Box<dyn Error>> {
+    let tld: DomainName = ".abc".parse()?;
+    assert!(tld.is_tld());
+    assert_eq!(tld.tld(), ".abc");
+    assert_eq!(tld.domain(), None);
+    assert_eq!(tld.to_string(), ".abc");
+
+    let domain: DomainName = "miester.abc".parse()?;
+    assert!(!domain.is_tld());
+    assert!(!domain.is_subdomain());
+    assert_eq!(domain.tld(), ".abc");
+    assert_eq!(domain.domain(), Some("miester"));
+    assert_eq!(domain.to_string(), "miester.abc");
+
+    let subdomain: DomainName = "sub.miester.abc".parse()?;
+    assert!(subdomain.is_subdomain());
+    assert_eq!(subdomain.subdomain(), Some("sub"));
+    assert_eq!(subdomain.record(), None);
+    assert_eq!(subdomain.to_string(), "sub.miester.abc");
+
+    let record: DomainName = "SOL.sub.miester.abc".parse()?;
+    assert_eq!(record.record(), Some("SOL"));
+    assert_eq!(record.subdomain(), Some("sub"));
+    assert_eq!(record.to_string(), "SOL.sub.miester.abc");
+    Ok(())
+}
+
+#[test]
+fn lowercases_labels() -> Result<(), Box<dyn Error>> {
+    let domain: DomainName = "Sub.MIESTER.Abc".parse()?;
+    assert_eq!(domain.to_string(), "sub.miester.abc");
+    assert_eq!(domain, "sub.miester.abc".parse()?);
+    Ok(())
+}
+
+#[test]
+fn rejects_invalid_names() {
+    let too_long_label = format!("{}.abc", "a".repeat(DomainName::MAX_LABEL_LEN + 1));
+    let too_long_name = format!("{}.abc", ["a"; 130].join("."));
+    for invalid in [
+        "",
+        ".",
+        "miester",
+        "miester.",
+        ".miester.abc",
+        "miester..abc",
+        "mies ter.abc",
+        "mies/ter.abc",
+        "miester.abc\n",
+        "a.b.c.d.abc",
+        &too_long_label,
+        &too_long_name,
+    ] {
+        assert!(
+            matches!(
+                invalid.parse::<DomainName>(),
+                Err(TldParserError::InvalidDomain(_))
+            ),
+            "{:?} should be rejected",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn domain_key_is_case_insensitive_except_for_records() -> Result<(), Box<dyn Error>> {
+    assert_eq!(
+        get_domain_key("Miester.ABC", false)?.pubkey,
+        get_domain_key("miester.abc", false)?.pubkey
+    );
+
+    // record labels are hashed as is
+    let domain_key = get_domain_key("miester.abc", false)?.pubkey;
+    let (sol_record, _) = find_name_account_from_name("1SOL", None, Some(&domain_key));
+    assert_eq!(get_domain_key("SOL.miester.abc", true)?.pubkey, sol_record);
+    assert_ne!(get_domain_key("sol.miester.abc", true)?.pubkey, sol_record);
+    Ok(())
+}
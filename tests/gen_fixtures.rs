@@ -0,0 +1,368 @@
+//! Generates the synthetic account fixtures in tests/fixtures, see tests/fixtures/README.md.
+//!
+//! The layouts written here are the ones `src/state` decodes, not recorded mainnet accounts.
+//! Ignored by default as it rewrites the fixtures:
+//!
+//! ```sh
+//! cargo test --test gen_fixtures -- --ignored
+//! ```
+mod common;
+
+use common::FIXTURES_DIR;
+use serde_json::{json, Map, Value};
+use solana_account_decoder::{UiAccount, UiAccountEncoding};
+use solana_sdk::signer::Signer;
+use solana_sdk::{
+    account::Account, hash::hash, program_option::COption, program_pack::Pack, pubkey,
+    pubkey::Pubkey,
+};
+use spl_token_2022::state::{Account as TokenAccount, AccountState, Mint};
+use tldparser::{name_record_handler::get_domain_key, *};
+
+const OWNER: Pubkey = pubkey!("2EGGxj2qbNAJNgLCPKca8sxZYetyTjnoRspTPjzN2D67");
+const AUTHORITY: Pubkey = pubkey!("8XVnbeamKFPJGq1QdrMXq7ndvhiP8Dgm1HEvnLpAGP3e");
+const ATA_PROGRAM_ID: Pubkey = pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+const SLOT: u64 = 250_000_000;
+
+fn disc(name: &str) -> Vec<u8> {
+    hash(format!("account:{}", name).as_bytes()).to_bytes()[..8].to_vec()
+}
+fn s(v: &str) -> Vec<u8> {
+    let mut d = (v.len() as u32).to_le_bytes().to_vec();
+    d.extend_from_slice(v.as_bytes());
+    d
+}
+fn acc(owner: Pubkey, data: Vec<u8>) -> Account {
+    Account {
+        lamports: (128 + data.len() as u64) * 6960,
+        data,
+        owner,
+        executable: false,
+        rent_epoch: u64::MAX,
+    }
+}
+fn header(
+    parent: Pubkey,
+    owner: Pubkey,
+    class: Pubkey,
+    expires_at: u64,
+    created_at: u64,
+) -> Vec<u8> {
+    let mut d = vec![0; 8];
+    d.extend_from_slice(parent.as_ref());
+    d.extend_from_slice(owner.as_ref());
+    d.extend_from_slice(class.as_ref());
+    d.extend_from_slice(&expires_at.to_le_bytes());
+    d.extend_from_slice(&created_at.to_le_bytes());
+    d.resize(NameRecordHeader::LEN, 0);
+    d
+}
+fn entry(map: &mut Map<String, Value>, key: Pubkey, account: &Account) {
+    let ui = UiAccount::encode(&key, account, UiAccountEncoding::Base64, None, None);
+    map.insert(key.to_string(), json!(ui));
+}
+fn write(name: &str, map: Map<String, Value>) {
+    let path = format!("{}/{}.json", FIXTURES_DIR, name);
+    std::fs::write(
+        &path,
+        serde_json::to_string_pretty(&Value::Object(map)).unwrap() + "\n",
+    )
+    .unwrap();
+    println!("wrote {}", path);
+}
+
+fn tld_house_data(tld: &str) -> Vec<u8> {
+    let (treasury, _) = find_tld_house_treasury(tld);
+    let mut d = disc("TldHouse");
+    d.extend_from_slice(AUTHORITY.as_ref());
+    d.extend_from_slice(treasury.as_ref());
+    d.extend_from_slice(AUTHORITY.as_ref());
+    d.extend(s(tld));
+    // the configuration after the tld is not decoded
+    d.resize(300, 0);
+    d
+}
+
+fn reverse_lookup(map: &mut Map<String, Value>, name_account: &Pubkey, tld: &str, label: &str) {
+    let (tld_house, _) = find_tld_house(tld);
+    let (key, _) = find_name_account_from_hashed_name(
+        &get_hashed_name(&name_account.to_string()),
+        Some(&tld_house),
+        None,
+    );
+    let mut d = header(Pubkey::default(), tld_house, tld_house, 0, 1_677_000_000);
+    d.extend_from_slice(label.as_bytes());
+    entry(map, key, &acc(ANS_PROGRAM_ID, d));
+}
+
+fn domain(
+    map: &mut Map<String, Value>,
+    label: &str,
+    tld: &str,
+    owner: Pubkey,
+    expires_at: u64,
+) -> Pubkey {
+    let parent = get_name_parent_from_tld(tld);
+    let (key, _) = find_name_account_from_name(label, None, Some(&parent));
+    entry(
+        map,
+        key,
+        &acc(
+            ANS_PROGRAM_ID,
+            header(parent, owner, Pubkey::default(), expires_at, 1_677_000_000),
+        ),
+    );
+    reverse_lookup(map, &key, tld, label);
+    key
+}
+
+fn subdomain(map: &mut Map<String, Value>, name: &str, owner: Pubkey, expires_at: u64) -> Pubkey {
+    let key = get_domain_key(name, false).unwrap();
+    entry(
+        map,
+        key.pubkey,
+        &acc(
+            ANS_PROGRAM_ID,
+            header(
+                key.parent.unwrap(),
+                owner,
+                Pubkey::default(),
+                expires_at,
+                1_690_000_000,
+            ),
+        ),
+    );
+    key.pubkey
+}
+
+#[test]
+#[ignore = "rewrites tests/fixtures"]
+fn gen_fixtures() {
+    // tlds
+    let mut tlds = Map::new();
+    for tld in [".abc", ".bonk"] {
+        let (tld_house, _) = find_tld_house(tld);
+        let parent = get_name_parent_from_tld(tld);
+        entry(
+            &mut tlds,
+            parent,
+            &acc(
+                ANS_PROGRAM_ID,
+                header(
+                    ORIGIN_TLD_KEY,
+                    tld_house,
+                    Pubkey::default(),
+                    0,
+                    1_670_000_000,
+                ),
+            ),
+        );
+        entry(
+            &mut tlds,
+            tld_house,
+            &acc(TLD_HOUSE_PROGRAM_ID, tld_house_data(tld)),
+        );
+    }
+    write("tlds", tlds);
+
+    // miester.abc with a url record and the owner's main domain
+    let mut miester = Map::new();
+    let miester_key = domain(&mut miester, "miester", ".abc", OWNER, 0);
+    let url = get_domain_key("url.miester.abc", true).unwrap().pubkey;
+    let mut url_data = header(miester_key, OWNER, Pubkey::default(), 0, 1_680_000_000);
+    url_data.extend(s("https://alldomains.id"));
+    entry(&mut miester, url, &acc(ANS_PROGRAM_ID, url_data));
+    let (main_domain, _) = find_main_domain(&OWNER);
+    let mut main_data = disc("MainDomain");
+    main_data.extend_from_slice(miester_key.as_ref());
+    main_data.extend(s(".abc"));
+    main_data.extend(s("miester"));
+    main_data.resize(MainDomain::MAIN_DOMAIN_SIZE, 0);
+    entry(
+        &mut miester,
+        main_domain,
+        &acc(TLD_HOUSE_PROGRAM_ID, main_data),
+    );
+    reverse_lookup(&mut miester, &url, ".abc", "1url");
+    // SOL record without reverse lookup, pic record left by a previous owner with a broken payload
+    let sol = get_domain_key("SOL.miester.abc", true).unwrap().pubkey;
+    let mut sol_data = header(miester_key, OWNER, Pubkey::default(), 0, 1_680_000_000);
+    sol_data.extend(s(&OWNER.to_string()));
+    entry(&mut miester, sol, &acc(ANS_PROGRAM_ID, sol_data));
+    let pic = get_domain_key("pic.miester.abc", true).unwrap().pubkey;
+    let mut pic_data = header(miester_key, AUTHORITY, Pubkey::default(), 0, 1_650_000_000);
+    pic_data.extend_from_slice(&4u32.to_le_bytes());
+    pic_data.extend_from_slice(&[0xf0, 0x28, 0x8c, 0x28]);
+    entry(&mut miester, pic, &acc(ANS_PROGRAM_ID, pic_data));
+    let sub = subdomain(&mut miester, "sub.miester.abc", AUTHORITY, 0);
+    reverse_lookup(&mut miester, &sub, ".abc", "0sub");
+    // url.sub.miester.abc lives under the "\0sub" subdomain
+    let sub_url = get_domain_key("url.sub.miester.abc", true).unwrap().pubkey;
+    let (sub_record_parent, _) =
+        find_name_account_from_hashed_name(&get_hashed_name("\0sub"), None, Some(&miester_key));
+    entry(
+        &mut miester,
+        sub_record_parent,
+        &acc(
+            ANS_PROGRAM_ID,
+            header(miester_key, AUTHORITY, Pubkey::default(), 0, 1_690_000_000),
+        ),
+    );
+    reverse_lookup(&mut miester, &sub_record_parent, ".abc", "\0sub");
+    let mut sub_url_data = header(
+        sub_record_parent,
+        AUTHORITY,
+        Pubkey::default(),
+        0,
+        1_690_000_000,
+    );
+    sub_url_data.extend(s("https://sub.alldomains.id"));
+    entry(&mut miester, sub_url, &acc(ANS_PROGRAM_ID, sub_url_data));
+    reverse_lookup(&mut miester, &sub_url, ".abc", "1url");
+    write("miester.abc", miester);
+
+    // cicu.abc, wrapped into a token-2022 nft held by the owner
+    let mut cicu = Map::new();
+    let (tld_house, _) = find_tld_house(".abc");
+    let (name_house, _) = find_name_house(&tld_house);
+    let parent = get_name_parent_from_tld(".abc");
+    let (cicu_key, _) = find_name_account_from_name("cicu", None, Some(&parent));
+    let (nft_record, nft_record_bump) = find_nft_record(&cicu_key, &name_house);
+    let (mint, _) = find_mint_address(&cicu_key, &name_house);
+    domain(&mut cicu, "cicu", ".abc", nft_record, 0);
+    let mut nft_data = disc("NftRecord");
+    nft_data.push(1);
+    nft_data.push(nft_record_bump);
+    nft_data.extend_from_slice(cicu_key.as_ref());
+    nft_data.extend_from_slice(OWNER.as_ref());
+    nft_data.extend_from_slice(mint.as_ref());
+    nft_data.extend_from_slice(tld_house.as_ref());
+    nft_data.resize(NftRecord::LEN, 0);
+    entry(&mut cicu, nft_record, &acc(NAME_HOUSE_PROGRAM_ID, nft_data));
+    let mut mint_data = vec![0; Mint::LEN];
+    Mint {
+        mint_authority: COption::Some(nft_record),
+        supply: 1,
+        decimals: 0,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+    .pack_into_slice(&mut mint_data);
+    entry(&mut cicu, mint, &acc(spl_token_2022::id(), mint_data));
+    let (ata, _) = Pubkey::find_program_address(
+        &[OWNER.as_ref(), spl_token_2022::id().as_ref(), mint.as_ref()],
+        &ATA_PROGRAM_ID,
+    );
+    let mut token_data = vec![0; TokenAccount::LEN];
+    TokenAccount {
+        mint,
+        owner: OWNER,
+        amount: 1,
+        delegate: COption::None,
+        state: AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    }
+    .pack_into_slice(&mut token_data);
+    entry(&mut cicu, ata, &acc(spl_token_2022::id(), token_data));
+    write("cicu.abc", cicu);
+
+    // onsol.bonk expiring in 2100, expired.abc expired in 2020
+    let mut onsol = Map::new();
+    domain(&mut onsol, "onsol", ".bonk", OWNER, 4_102_444_800);
+    write("onsol.bonk", onsol);
+    let mut expired = Map::new();
+    domain(&mut expired, "expired", ".abc", OWNER, 1_600_000_000);
+    subdomain(&mut expired, "old.expired.abc", AUTHORITY, 0);
+    write("expired.abc", expired);
+
+    // signed.abc with signed records, an outdated record, a badly signed one, a truncated one and one with leftover bytes
+    let mut signed = Map::new();
+    let signer = solana_sdk::signer::keypair::keypair_from_seed(&[1; 32]).unwrap();
+    let wallet = solana_sdk::signer::keypair::keypair_from_seed(&[2; 32]).unwrap();
+    let signed_key = domain(&mut signed, "signed", ".abc", signer.pubkey(), 0);
+    for (record, value, sig_by, created_at) in [
+        (
+            "SOL",
+            wallet.pubkey().to_string(),
+            Some(&wallet),
+            1_690_000_000,
+        ),
+        (
+            "url",
+            "https://signed.alldomains.id".to_string(),
+            Some(&signer),
+            1_690_000_000,
+        ),
+        (
+            "email",
+            "signed@alldomains.id".to_string(),
+            Some(&wallet),
+            1_690_000_000,
+        ),
+        ("twitter", "@signed".to_string(), None, 1_600_000_000),
+    ] {
+        let key = get_domain_key(&format!("{}.signed.abc", record), true)
+            .unwrap()
+            .pubkey;
+        let mut d = header(
+            signed_key,
+            signer.pubkey(),
+            Pubkey::default(),
+            0,
+            created_at,
+        );
+        let signature = sig_by.map(|keypair| {
+            keypair.sign_message(&get_record_signature_message(
+                &key,
+                &signer.pubkey(),
+                &value,
+            ))
+        });
+        d.extend(get_record_payload(&value, signature.as_ref()));
+        d.resize(d.len() + 32, 0);
+        entry(&mut signed, key, &acc(ANS_PROGRAM_ID, d));
+    }
+    // a github record whose signature is cut short
+    let key = get_domain_key("github.signed.abc", true).unwrap().pubkey;
+    let mut d = header(
+        signed_key,
+        signer.pubkey(),
+        Pubkey::default(),
+        0,
+        1_690_000_000,
+    );
+    let message = get_record_signature_message(&key, &signer.pubkey(), "signed");
+    d.extend(get_record_payload(
+        "signed",
+        Some(&signer.sign_message(&message)),
+    ));
+    d.truncate(d.len() - 24);
+    entry(&mut signed, key, &acc(ANS_PROGRAM_ID, d));
+    // a discord record with leftover bytes from another writer, not a signature
+    let key = get_domain_key("discord.signed.abc", true).unwrap().pubkey;
+    let mut d = header(
+        signed_key,
+        signer.pubkey(),
+        Pubkey::default(),
+        0,
+        1_690_000_000,
+    );
+    d.extend(s("signed#0001"));
+    d.extend_from_slice(&[0xab; 16]);
+    entry(&mut signed, key, &acc(ANS_PROGRAM_ID, d));
+    write("signed.abc", signed);
+
+    // clock sysvar at 2023-11-14
+    let mut clock = Map::new();
+    let clock_account = solana_sdk::account::create_account_for_test(&solana_sdk::clock::Clock {
+        slot: SLOT,
+        epoch_start_timestamp: 1_699_900_000,
+        epoch: 578,
+        leader_schedule_epoch: 579,
+        unix_timestamp: 1_700_000_000,
+    });
+    entry(&mut clock, solana_sdk::sysvar::clock::id(), &clock_account);
+    write("clock", clock);
+}
@@ -0,0 +1,143 @@
+mod common;
+
+use {
+    common::*,
+    solana_sdk::{instruction::AccountMeta, pubkey, pubkey::Pubkey},
+    std::error::Error,
+    tldparser::{instructions::*, name_record_handler::get_domain_key, *},
+};
+
+const OWNER: Pubkey = pubkey!("2EGGxj2qbNAJNgLCPKca8sxZYetyTjnoRspTPjzN2D67");
+#[tokio::test]
+async fn transfer_domain_instructions() -> Result<(), Box<dyn Error>> {
+    let parser = fixture_parser(Some(FIXTURE_NOW))?;
+    let new_owner = Pubkey::new_unique();
+
+    // ans transfers of unwrapped domains are not built
+    assert!(matches!(
+        parser
+            .transfer_domain_instructions(&"miester.abc".parse()?, &new_owner)
+            .await,
+        Err(TldParserError::UnsupportedInstruction(_))
+    ));
+
+    // wrapped domains move their nft from the holder to a new token account
+    let cicu: DomainName = "cicu.abc".parse()?;
+    let instructions = parser
+        .transfer_domain_instructions(&cicu, &new_owner)
+        .await?;
+    assert_eq!(instructions.len(), 2);
+    let cicu_account = get_domain_key("cicu.abc", false)?.pubkey;
+    let (mint, _) = find_mint_address(&cicu_account, &find_name_house(&find_tld_house(".abc").0).0);
+    let ata = |wallet: &Pubkey| {
+        Pubkey::find_program_address(
+            &[
+                wallet.as_ref(),
+                spl_token_2022::id().as_ref(),
+                mint.as_ref(),
+            ],
+            &pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"),
+        )
+        .0
+    };
+    let create_ata = &instructions[0];
+    assert_eq!(
+        create_ata.program_id,
+        pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")
+    );
+    assert_eq!(create_ata.accounts[0], AccountMeta::new(OWNER, true));
+    assert_eq!(create_ata.accounts[1].pubkey, ata(&new_owner));
+    assert_eq!(create_ata.accounts[2].pubkey, new_owner);
+    assert_eq!(create_ata.accounts[3].pubkey, mint);
+
+    let transfer = &instructions[1];
+    assert_eq!(transfer.program_id, spl_token_2022::id());
+    assert_eq!(
+        spl_token_2022::instruction::TokenInstruction::unpack(&transfer.data)?,
+        spl_token_2022::instruction::TokenInstruction::TransferChecked {
+            amount: 1,
+            decimals: 0
+        }
+    );
+    assert_eq!(
+        transfer
+            .accounts
+            .iter()
+            .map(|account| account.pubkey)
+            .collect::<Vec<_>>(),
+        [ata(&OWNER), mint, ata(&new_owner), OWNER]
+    );
+
+    // the offline builder takes the resolution, with the token account holding the nft
+    let resolution = parser.resolve_domain(&cicu).await?;
+    assert_eq!(resolution.token_account, Some(ata(&OWNER)));
+    assert_eq!(
+        transfer_domain(&cicu, &resolution, &new_owner)?,
+        instructions
+    );
+    assert!(is_wrapped_domain(
+        &cicu_account,
+        ".abc",
+        &resolution.name_record.owner
+    ));
+
+    // the nft is sent from the account holding it, even when it is not the holder's ata
+    let mut accounts = fixture_accounts()?;
+    let token_account = accounts.remove(&ata(&OWNER)).unwrap();
+    let other_token_account = Pubkey::new_unique();
+    accounts.insert(other_token_account, token_account);
+    let parser = parser_over(accounts, Some(FIXTURE_NOW));
+    let instructions = parser
+        .transfer_domain_instructions(&cicu, &new_owner)
+        .await?;
+    assert_eq!(instructions[1].accounts[0].pubkey, other_token_account);
+    assert_eq!(instructions[1].accounts[3].pubkey, OWNER);
+
+    let mut resolution = parser.resolve_domain(&cicu).await?;
+    resolution.token_account = None;
+    assert!(matches!(
+        transfer_domain(&cicu, &resolution, &new_owner),
+        Err(TldParserError::WrappedOwnerUnresolvable(key)) if key == mint
+    ));
+
+    assert!(matches!(
+        parser
+            .transfer_domain_instructions(&"expired.abc".parse()?, &new_owner)
+            .await,
+        Err(TldParserError::Expired { .. })
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn wrapped_domain_accounts() -> Result<(), Box<dyn Error>> {
+    let parser = fixture_parser(Some(FIXTURE_NOW))?;
+    let cicu: DomainName = "cicu.abc".parse()?;
+    let accounts = WrappedDomainAccounts::new(&cicu)?;
+
+    // the derived accounts match the wrapped fixture
+    let cicu_record = parser.get_name_record_from_domain_tld(&cicu).await?;
+    assert_eq!(cicu_record.owner, accounts.nft_record);
+    let nft_record = NftRecord::from_account_info(
+        &parser
+            .account_source
+            .get_account_data(&accounts.nft_record)
+            .await?,
+    )?;
+    assert_eq!(nft_record.name_account, accounts.name_account);
+    assert_eq!(nft_record.nft_mint_account, accounts.mint);
+    let holder_token_account = parser
+        .account_source
+        .get_token_largest_accounts(&accounts.mint)
+        .await?;
+    assert_eq!(
+        holder_token_account[0].address,
+        accounts.token_account(&OWNER).to_string()
+    );
+
+    assert!(matches!(
+        WrappedDomainAccounts::new(&"sub.miester.abc".parse()?),
+        Err(TldParserError::InvalidDomain(_))
+    ));
+    Ok(())
+}
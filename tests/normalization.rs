@@ -0,0 +1,129 @@
+use std::error::Error;
+use tldparser::{name_record_handler::get_domain_key, *};
+
+#[test]
+fn canonical_forms_derive_the_same_account() -> Result<(), Box<dyn Error>> {
+    let nfc: DomainName = "caf\u{e9}.abc".parse()?;
+    let nfd: DomainName = "cafe\u{301}.abc".parse()?;
+    let upper: DomainName = "CAF\u{c9}.abc".parse()?;
+    let punycode: DomainName = "xn--caf-dma.abc".parse()?;
+    assert_eq!(nfc, nfd);
+    assert_eq!(nfc, upper);
+    assert_eq!(nfc, punycode);
+    assert_eq!(nfc.to_string(), "caf\u{e9}.abc");
+
+    let key = get_domain_key("caf\u{e9}.abc", false)?.pubkey;
+    for variant in [
+        "cafe\u{301}.abc",
+        "CAF\u{c9}.abc",
+        "xn--caf-dma.abc",
+        "CAFE\u{301}.ABC",
+    ] {
+        assert_eq!(get_domain_key(variant, false)?.pubkey, key, "{}", variant);
+    }
+    Ok(())
+}
+
+#[test]
+fn case_folding_vectors() -> Result<(), Box<dyn Error>> {
+    let vectors = [
+        // sharp s, lowercase and capital
+        ("stra\u{df}e", "strasse"),
+        ("STRA\u{1e9e}E", "strasse"),
+        ("Strasse", "strasse"),
+        // final and capital sigma
+        (
+            "\u{3bf}\u{3b4}\u{3bf}\u{3c2}",
+            "\u{3bf}\u{3b4}\u{3bf}\u{3c3}",
+        ),
+        (
+            "\u{39f}\u{394}\u{39f}\u{3a3}",
+            "\u{3bf}\u{3b4}\u{3bf}\u{3c3}",
+        ),
+        // fullwidth forms
+        (
+            "\u{ff2d}\u{ff49}\u{ff45}\u{ff53}\u{ff54}\u{ff45}\u{ff52}",
+            "miester",
+        ),
+        // emoji have no case, zero width joiners are kept
+        ("\u{1f525}", "\u{1f525}"),
+        (
+            "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}",
+            "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}",
+        ),
+    ];
+    for (label, canonical) in vectors {
+        assert_eq!(canonicalize_label(label), canonical, "{}", label);
+        assert_eq!(canonicalize_label(canonical), canonical, "{}", canonical);
+        assert_eq!(
+            get_domain_key(&format!("{}.abc", label), false)?.pubkey,
+            get_domain_key(&format!("{}.abc", canonical), false)?.pubkey,
+            "{}",
+            label
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn tld_derivation_is_normalized() {
+    assert_eq!(find_tld_house(".ABC"), find_tld_house(".abc"));
+    assert_eq!(
+        get_name_parent_from_tld(".ABC"),
+        get_name_parent_from_tld(".abc")
+    );
+}
+
+#[test]
+fn punycode_round_trip() -> Result<(), Box<dyn Error>> {
+    let vectors = [
+        ("caf\u{e9}.abc", "xn--caf-dma.abc"),
+        ("m\u{fc}nchen.bonk", "xn--mnchen-3ya.bonk"),
+        ("\u{65e5}\u{672c}.abc", "xn--wgv71a.abc"),
+        ("miester.abc", "miester.abc"),
+    ];
+    for (display, ascii) in vectors {
+        let from_display: DomainName = display.parse()?;
+        let from_ascii: DomainName = ascii.parse()?;
+        assert_eq!(from_display, from_ascii);
+        assert_eq!(from_display.to_string(), display);
+        assert_eq!(from_display.to_ascii()?, ascii);
+    }
+    Ok(())
+}
+
+#[test]
+fn emoji_domains() -> Result<(), Box<dyn Error>> {
+    let vectors = [
+        ("\u{1f525}.abc", "xn--4v8h.abc"),
+        ("\u{1f600}\u{1f600}.abc", "xn--e28ha.abc"),
+        ("sub.\u{1f525}.abc", "sub.xn--4v8h.abc"),
+    ];
+    for (display, ascii) in vectors {
+        let domain: DomainName = display.parse()?;
+        assert_eq!(domain.to_ascii()?, ascii);
+        assert_eq!(ascii.parse::<DomainName>()?, domain);
+        assert_eq!(
+            get_domain_key(display, false)?.pubkey,
+            get_domain_key(ascii, false)?.pubkey
+        );
+    }
+
+    // emoji have no case, the hashed form is the emoji itself
+    let fire = get_domain_key("\u{1f525}.abc", false)?;
+    assert_eq!(fire.hashed, get_hashed_name("\u{1f525}"));
+    Ok(())
+}
+
+#[test]
+fn label_helpers() -> Result<(), Box<dyn Error>> {
+    assert_eq!(canonicalize_label("Cafe\u{301}"), "caf\u{e9}");
+    assert_eq!(normalize_label("XN--CAF-DMA")?, "caf\u{e9}");
+    assert_eq!(decode_punycode_label("miester")?, "miester");
+    assert_eq!(encode_punycode_label("caf\u{e9}")?, "xn--caf-dma");
+    assert!(matches!(
+        decode_punycode_label("xn--99999999999"),
+        Err(TldParserError::InvalidDomain(_))
+    ));
+    Ok(())
+}
@@ -0,0 +1,352 @@
+mod common;
+
+use {
+    common::*,
+    solana_sdk::{pubkey, pubkey::Pubkey, signature::Signature},
+    std::error::Error,
+    tldparser::{name_record_handler::get_domain_key, *},
+};
+
+const OWNER: Pubkey = pubkey!("2EGGxj2qbNAJNgLCPKca8sxZYetyTjnoRspTPjzN2D67");
+
+#[tokio::test]
+async fn all_records() -> Result<(), Box<dyn Error>> {
+    let parser = fixture_parser(Some(FIXTURE_NOW))?;
+    let records = parser.get_all_records(&"miester.abc".parse()?).await?;
+    assert_eq!(
+        records.keys().copied().collect::<Vec<_>>(),
+        [Record::SOL, Record::Url, Record::Pic]
+    );
+
+    let url = records[&Record::Url].as_ref().unwrap();
+    assert_eq!(url.record, Record::Url);
+    assert_eq!(
+        url.name_account,
+        get_domain_key("url.miester.abc", true)?.pubkey
+    );
+    assert_eq!(url.value.as_deref().ok(), Some("https://alldomains.id"));
+    assert_eq!(url.validity, Validity::NonExpiring);
+    assert_eq!(
+        records[&Record::SOL]
+            .as_ref()
+            .unwrap()
+            .value
+            .as_deref()
+            .ok(),
+        Some(OWNER.to_string().as_str())
+    );
+    // a broken payload does not hide the other records
+    assert!(matches!(
+        records[&Record::Pic].as_ref().unwrap().value,
+        Err(TldParserError::InvalidUtf8(_))
+    ));
+
+    // records of a subdomain
+    let sub_records = parser.get_all_records(&"sub.miester.abc".parse()?).await?;
+    assert_eq!(sub_records.len(), 1);
+    assert_eq!(
+        sub_records[&Record::Url]
+            .as_ref()
+            .unwrap()
+            .value
+            .as_deref()
+            .ok(),
+        Some("https://sub.alldomains.id")
+    );
+
+    assert!(parser
+        .get_all_records(&"onsol.bonk".parse()?)
+        .await?
+        .is_empty());
+    assert!(matches!(
+        parser.get_all_records(&".abc".parse()?).await,
+        Err(TldParserError::InvalidDomain(_))
+    ));
+
+    let record_accounts =
+        find_domain_name_records(parser.account_source.as_ref(), "miester.abc").await?;
+    assert_eq!(record_accounts.len(), 3);
+    assert_eq!(record_accounts[1].1, url.name_account);
+    Ok(())
+}
+
+#[tokio::test]
+async fn all_records_isolate_malformed_headers() -> Result<(), Box<dyn Error>> {
+    // the pic record cut inside its header
+    let mut account_source = fixture_accounts()?;
+    let pic = get_domain_key("pic.miester.abc", true)?.pubkey;
+    let mut account = account_source.get(&pic).unwrap().clone();
+    account.data.truncate(100);
+    account_source.insert(pic, account);
+    let parser = parser_over(account_source, Some(FIXTURE_NOW));
+
+    let records = parser.get_all_records(&"miester.abc".parse()?).await?;
+    assert_eq!(records.len(), 3);
+    assert!(matches!(
+        records[&Record::Pic],
+        Err(TldParserError::Deserialization(_))
+    ));
+    assert_eq!(
+        records[&Record::Url]
+            .as_ref()
+            .unwrap()
+            .value
+            .as_deref()
+            .ok(),
+        Some("https://alldomains.id")
+    );
+    assert!(records[&Record::SOL].is_ok());
+    Ok(())
+}
+
+#[test]
+fn record_list() {
+    assert_eq!(Record::ALL.len(), 18);
+    assert_eq!(get_record_string(Record::ALL[5]), "Lattica");
+    assert!(Record::ALL.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[tokio::test]
+async fn record_values() -> Result<(), Box<dyn Error>> {
+    let parser = fixture_parser(Some(FIXTURE_NOW))?;
+    let records = parser.get_all_records(&"miester.abc".parse()?).await?;
+    assert_eq!(
+        records[&Record::SOL].as_ref().unwrap().parse_value()?,
+        RecordValue::Sol(OWNER)
+    );
+    assert_eq!(
+        records[&Record::Url]
+            .as_ref()
+            .unwrap()
+            .parse_value()?
+            .to_string(),
+        "https://alldomains.id/"
+    );
+    assert!(matches!(
+        records[&Record::Pic].as_ref().unwrap().parse_value(),
+        Err(TldParserError::InvalidRecordValue {
+            record: Record::Pic,
+            ..
+        })
+    ));
+    assert_eq!(
+        get_record_value(parser.account_source.as_ref(), "miester.abc", Record::SOL).await?,
+        RecordValue::Sol(OWNER)
+    );
+    Ok(())
+}
+
+#[test]
+fn record_value_parsers() {
+    let valid = [
+        (
+            Record::SOL,
+            " 2EGGxj2qbNAJNgLCPKca8sxZYetyTjnoRspTPjzN2D67\0",
+        ),
+        (Record::ETH, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+        (Record::BTC, "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa"),
+        (Record::BTC, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+        (
+            Record::BTC,
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+        ),
+        (
+            Record::IPFS,
+            "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+        ),
+        (
+            Record::IPFS,
+            "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        (Record::ARWV, "bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U"),
+        (Record::Url, "https://alldomains.id"),
+        (Record::Email, "miester@alldomains.id"),
+        (Record::Twitter, "@miester"),
+    ];
+    for (record, value) in valid {
+        assert!(
+            RecordValue::parse(record, value).is_ok(),
+            "{:?} {}",
+            record,
+            value
+        );
+    }
+
+    // single case eth addresses are returned checksummed
+    assert_eq!(
+        RecordValue::parse(Record::ETH, "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap(),
+        RecordValue::Eth("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed".to_string())
+    );
+    assert_eq!(
+        RecordValue::parse(
+            Record::IPFS,
+            "ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"
+        )
+        .unwrap()
+        .to_string(),
+        "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"
+    );
+
+    let invalid = [
+        (Record::SOL, "not a pubkey"),
+        (Record::SHDW, ""),
+        (Record::ETH, "0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+        (Record::ETH, "5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"),
+        (Record::BTC, "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb"),
+        (Record::BTC, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5"),
+        // corrupted taproot address
+        (
+            Record::BTC,
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd",
+        ),
+        // bitcoin address in a litecoin record
+        (Record::LTC, "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa"),
+        (Record::DOGE, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+        (
+            Record::IPFS,
+            "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbd",
+        ),
+        (
+            Record::IPFS,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzd",
+        ),
+        (Record::ARWV, "bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt"),
+        (Record::Url, "alldomains"),
+        (Record::Email, "miester@alldomains"),
+        (Record::Email, "miester.alldomains.id"),
+    ];
+    for (record, value) in invalid {
+        assert!(
+            matches!(
+                RecordValue::parse(record, value),
+                Err(TldParserError::InvalidRecordValue { record: invalid_record, .. })
+                    if invalid_record == record
+            ),
+            "{:?} {}",
+            record,
+            value
+        );
+    }
+}
+
+#[tokio::test]
+async fn record_staleness() -> Result<(), Box<dyn Error>> {
+    let parser = fixture_parser(Some(FIXTURE_NOW))?;
+    let miester: DomainName = "miester.abc".parse()?;
+
+    let url = parser.get_record_v2(&miester, Record::Url).await?;
+    assert_eq!(url.staleness, Staleness::Fresh);
+    assert_eq!(url.domain.owner, OWNER);
+    assert_eq!(url.ensure_fresh()?.name_account, url.entry.name_account);
+    assert!(matches!(url.signature, Ok(None)));
+    assert!(!url.verify_owner_signature());
+
+    // left behind by the previous owner
+    let pic = parser.get_record_v2(&miester, Record::Pic).await?;
+    assert_eq!(
+        pic.staleness,
+        Staleness::OwnerChanged {
+            record_owner: pubkey!("8XVnbeamKFPJGq1QdrMXq7ndvhiP8Dgm1HEvnLpAGP3e")
+        }
+    );
+    assert!(matches!(
+        pic.ensure_fresh(),
+        Err(TldParserError::StaleRecord(key)) if key == pic.entry.name_account
+    ));
+
+    // records of a subdomain are checked against the subdomain owner
+    let sub_url = parser
+        .get_record_v2(&"sub.miester.abc".parse()?, Record::Url)
+        .await?;
+    assert!(!sub_url.is_stale());
+
+    assert!(matches!(
+        parser.get_record_v2(&miester, Record::ETH).await,
+        Err(TldParserError::AccountNotFound(_))
+    ));
+    assert!(matches!(
+        parser
+            .get_record_v2(&"url.sub.miester.abc".parse()?, Record::Url)
+            .await,
+        Err(TldParserError::InvalidDomain(_))
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn record_signatures() -> Result<(), Box<dyn Error>> {
+    let parser = fixture_parser(Some(FIXTURE_NOW))?;
+    let signed: DomainName = "signed.abc".parse()?;
+    let signer = pubkey!("AKnL4NNf3DGWZJS6cPknBuEGnVsV4A4m5tgebLHaRSZ9");
+    let wallet = pubkey!("9hSR6S7WPtxmTojgo6GG3k4yDPecgJY292j7xrsUGWBu");
+
+    let sol = parser.get_record_v2(&signed, Record::SOL).await?;
+    assert!(!sol.is_stale());
+    assert_eq!(sol.entry.parse_value()?, RecordValue::Sol(wallet));
+    assert!(sol.verify_right_of_association()?);
+    assert!(!sol.verify_owner_signature());
+    assert_eq!(
+        sol.signed_message()?,
+        get_record_signature_message(&sol.entry.name_account, &signer, &wallet.to_string())
+    );
+
+    let url = parser.get_record_v2(&signed, Record::Url).await?;
+    assert!(url.verify_owner_signature());
+    assert!(url.verify_signature(&signer));
+    assert!(matches!(
+        url.verify_right_of_association(),
+        Err(TldParserError::InvalidRecordValue { .. })
+    ));
+
+    let email = parser.get_record_v2(&signed, Record::Email).await?;
+    assert!(matches!(email.signature, Ok(Some(_))));
+    assert!(!email.verify_owner_signature());
+
+    // created before the name account of the domain, zero padding is no signature
+    let twitter = parser.get_record_v2(&signed, Record::Twitter).await?;
+    assert_eq!(
+        twitter.staleness,
+        Staleness::PredatesDomain {
+            created_at: 1_600_000_000,
+            domain_created_at: 1_677_000_000
+        }
+    );
+    assert!(matches!(twitter.signature, Ok(None)));
+
+    // a signature cut short is reported, not ignored
+    let github = parser.get_record_v2(&signed, Record::Github).await?;
+    assert!(!github.is_stale());
+    assert!(matches!(
+        github.signature,
+        Err(TldParserError::AccountDataTooShort { expected, actual })
+            if expected == actual + 24
+    ));
+    assert!(!github.verify_owner_signature());
+
+    // bytes after the payload without the signature marker are not a signature
+    let discord = parser.get_record_v2(&signed, Record::Discord).await?;
+    assert_eq!(discord.entry.value.as_deref().ok(), Some("signed#0001"));
+    assert!(matches!(discord.signature, Ok(None)));
+    Ok(())
+}
+
+#[test]
+fn record_payload() -> Result<(), Box<dyn Error>> {
+    // the payload decodes like an on chain record, with its signature
+    let signature = Signature::from([7; 64]);
+    let mut account_data = vec![0; NameRecordHeader::LEN];
+    account_data.extend(get_record_payload(&OWNER.to_string(), Some(&signature)));
+    assert_eq!(
+        NameRecordHeader::deserialize_data_string(&account_data)?,
+        OWNER.to_string()
+    );
+    assert_eq!(
+        NameRecordHeader::deserialize_record_signature(&account_data)?,
+        Some(signature)
+    );
+    assert_eq!(
+        get_record_payload("@miester", None),
+        [&8u32.to_le_bytes()[..], b"@miester"].concat()
+    );
+    Ok(())
+}
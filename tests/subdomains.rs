@@ -0,0 +1,222 @@
+mod common;
+
+use {
+    common::*,
+    solana_sdk::{pubkey, pubkey::Pubkey},
+    std::error::Error,
+    tldparser::{name_record_handler::get_domain_key, *},
+};
+
+const OWNER: Pubkey = pubkey!("2EGGxj2qbNAJNgLCPKca8sxZYetyTjnoRspTPjzN2D67");
+const AUTHORITY: Pubkey = pubkey!("8XVnbeamKFPJGq1QdrMXq7ndvhiP8Dgm1HEvnLpAGP3e");
+
+#[tokio::test]
+async fn subdomain_resolution() -> Result<(), Box<dyn Error>> {
+    let parser = fixture_parser(Some(FIXTURE_NOW))?;
+    let sub: DomainName = "sub.miester.abc".parse()?;
+    let sub_key = get_domain_key("sub.miester.abc", false)?;
+    let miester = get_domain_key("miester.abc", false)?.pubkey;
+
+    assert_eq!(parser.get_owner_from_domain_tld(&sub).await?, AUTHORITY);
+    let name_record = parser.get_name_record_from_domain_tld(&sub).await?;
+    assert_eq!(name_record.parent_name, miester);
+    assert!(name_record.is_valid);
+
+    let resolution = parser.resolve_domain(&sub).await?;
+    assert_eq!(resolution.name_account, sub_key.pubkey);
+    assert_eq!(resolution.owner, AUTHORITY);
+    assert_eq!(resolution.validity, Validity::NonExpiring);
+    assert_eq!(resolution.name_record, name_record);
+    let parent = resolution.parent.as_ref().unwrap();
+    assert_eq!(parent.name_account, miester);
+    assert_eq!(parent.owner, OWNER);
+    assert!(parent.parent.is_none());
+    assert!(resolution.is_valid());
+
+    // domains resolve the same as before
+    let domain = parser.resolve_domain(&"cicu.abc".parse()?).await?;
+    assert!(domain.parent.is_none());
+    assert_eq!(domain.owner, OWNER);
+    assert_ne!(domain.name_record.owner, OWNER);
+    Ok(())
+}
+
+#[tokio::test]
+async fn subdomain_of_expired_domain() -> Result<(), Box<dyn Error>> {
+    let parser = fixture_parser(Some(FIXTURE_NOW))?;
+    let old: DomainName = "old.expired.abc".parse()?;
+    let expired = get_domain_key("expired.abc", false)?.pubkey;
+
+    let resolution = parser.resolve_domain(&old).await?;
+    assert_eq!(resolution.validity, Validity::NonExpiring);
+    assert_eq!(
+        resolution.parent.as_ref().map(|parent| parent.validity),
+        Some(Validity::Expired)
+    );
+    assert!(!resolution.is_valid());
+    assert!(matches!(
+        parser.get_owner_from_domain_tld(&old).await,
+        Err(TldParserError::Expired { name_account, .. }) if name_account == expired
+    ));
+    assert!(!parser.get_name_record_from_domain_tld(&old).await?.is_valid);
+    Ok(())
+}
+
+#[tokio::test]
+async fn invalid_forward_names() -> Result<(), Box<dyn Error>> {
+    let parser = fixture_parser(Some(FIXTURE_NOW))?;
+    for name in [".abc", "url.sub.miester.abc"] {
+        let domain: DomainName = name.parse()?;
+        assert!(
+            matches!(
+                parser.get_owner_from_domain_tld(&domain).await,
+                Err(TldParserError::InvalidDomain(_))
+            ),
+            "{}",
+            name
+        );
+        assert!(matches!(
+            parser.get_name_record_from_domain_tld(&domain).await,
+            Err(TldParserError::InvalidDomain(_))
+        ));
+    }
+    assert!(matches!(
+        parser
+            .get_owner_from_domain_tld(&"missing.miester.abc".parse()?)
+            .await,
+        Err(TldParserError::AccountNotFound(_))
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn subdomain_reverse_lookup() -> Result<(), Box<dyn Error>> {
+    let parser = fixture_parser(Some(FIXTURE_NOW))?;
+    let vectors = [
+        ("miester.abc", false, NameKind::Domain, "miester", vec![]),
+        (
+            "sub.miester.abc",
+            false,
+            NameKind::Subdomain,
+            "sub",
+            vec!["miester"],
+        ),
+        (
+            "url.miester.abc",
+            true,
+            NameKind::Record,
+            "url",
+            vec!["miester"],
+        ),
+        (
+            "url.sub.miester.abc",
+            true,
+            NameKind::Record,
+            "url",
+            vec!["sub", "miester"],
+        ),
+    ];
+    for (name, record, kind, label, parent_labels) in vectors {
+        let name_key = get_domain_key(name, record)?;
+        let reverse_lookup = parser.reverse_lookup(&name_key.pubkey).await?;
+        assert_eq!(reverse_lookup.domain(), name);
+        assert_eq!(reverse_lookup.kind, kind, "{}", name);
+        assert_eq!(reverse_lookup.label, label);
+        assert_eq!(reverse_lookup.parent_labels, parent_labels);
+        assert_eq!(reverse_lookup.tld, ".abc");
+        assert_eq!(reverse_lookup.name_account, name_key.pubkey);
+        assert_eq!(
+            parser.reverse_lookup_name_account(&name_key.pubkey).await?,
+            label
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn prefixed_labels() {
+    assert_eq!(
+        NameKind::from_prefixed_label("0sub"),
+        (NameKind::Subdomain, "sub")
+    );
+    assert_eq!(
+        NameKind::from_prefixed_label("\0sub"),
+        (NameKind::Subdomain, "sub")
+    );
+    assert_eq!(
+        NameKind::from_prefixed_label("1SOL"),
+        (NameKind::Record, "SOL")
+    );
+    // only the first character is a prefix
+    assert_eq!(
+        NameKind::from_prefixed_label("01"),
+        (NameKind::Subdomain, "1")
+    );
+}
+
+#[tokio::test]
+async fn children_of_domain() -> Result<(), Box<dyn Error>> {
+    let parser = fixture_parser(Some(FIXTURE_NOW))?;
+    let miester: DomainName = "miester.abc".parse()?;
+
+    let subdomains = parser.get_subdomains(&miester).await?;
+    assert_eq!(subdomains.len(), 1);
+    assert_eq!(subdomains[0].domain(), "sub.miester.abc");
+    assert_eq!(subdomains[0].kind, NameKind::Subdomain);
+    assert_eq!(
+        subdomains[0].name_account,
+        get_domain_key("sub.miester.abc", false)?.pubkey
+    );
+    assert_eq!(subdomains[0].name_record.owner, AUTHORITY);
+
+    // the SOL and pic records have no reverse lookup, they are found by their key
+    let records = parser.get_records_of_domain(&miester).await?;
+    assert_eq!(
+        records.iter().map(|r| r.domain()).collect::<Vec<_>>(),
+        ["SOL.miester.abc", "pic.miester.abc", "url.miester.abc"]
+    );
+    assert!(records.iter().all(|r| r.kind == NameKind::Record));
+    assert_eq!(
+        records[2].name_account,
+        get_domain_key("url.miester.abc", true)?.pubkey
+    );
+    assert_eq!(
+        records[0].name_account,
+        get_domain_key("SOL.miester.abc", true)?.pubkey
+    );
+    assert!(matches!(
+        parser.reverse_lookup(&records[0].name_account).await,
+        Err(TldParserError::AccountNotFound(_))
+    ));
+
+    let sub_records = parser
+        .get_records_of_domain(&"sub.miester.abc".parse()?)
+        .await?;
+    assert_eq!(sub_records.len(), 1);
+    assert_eq!(sub_records[0].domain(), "url.sub.miester.abc");
+    assert_eq!(
+        sub_records[0].name_account,
+        get_domain_key("url.sub.miester.abc", true)?.pubkey
+    );
+    assert_eq!(
+        parser.reverse_lookup(&sub_records[0].name_account).await?,
+        sub_records[0]
+    );
+
+    // no children
+    assert!(parser
+        .get_subdomains(&"onsol.bonk".parse()?)
+        .await?
+        .is_empty());
+    assert!(matches!(
+        parser.get_subdomains(&"sub.miester.abc".parse()?).await,
+        Err(TldParserError::InvalidDomain(_))
+    ));
+    assert!(matches!(
+        parser
+            .get_records_of_domain(&"url.sub.miester.abc".parse()?)
+            .await,
+        Err(TldParserError::InvalidDomain(_))
+    ));
+    Ok(())
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use {
+    common::*,
+    solana_sdk::{account::Account, pubkey, pubkey::Pubkey, sysvar},
+    std::error::Error,
+    tldparser::*,
+};
+
+const OWNER: Pubkey = pubkey!("2EGGxj2qbNAJNgLCPKca8sxZYetyTjnoRspTPjzN2D67");
+/// expires_at of expired.abc in the fixtures
+const EXPIRES_AT: u64 = 1_600_000_000;
+
+#[tokio::test]
+async fn fixed_time_source() -> Result<(), Box<dyn Error>> {
+    let expired: DomainName = "expired.abc".parse()?;
+    let ends_at = EXPIRES_AT + DEFAULT_GRACE_PERIOD;
+
+    let parser = fixture_parser(Some(FixedTimeSource(EXPIRES_AT - 1)))?;
+    assert_eq!(parser.unix_timestamp().await?, EXPIRES_AT - 1);
+    let name_record = parser.get_name_record_from_domain_tld(&expired).await?;
+    assert_eq!(parser.validity(&name_record).await?, Validity::Active);
+    assert_eq!(parser.get_owner_from_domain_tld(&expired).await?, OWNER);
+
+    let parser = fixture_parser(Some(FixedTimeSource(EXPIRES_AT)))?;
+    assert_eq!(
+        parser.validity(&name_record).await?,
+        Validity::InGracePeriod { ends_at }
+    );
+    assert_eq!(parser.get_owner_from_domain_tld(&expired).await?, OWNER);
+
+    let parser = fixture_parser(Some(FixedTimeSource(ends_at)))?;
+    assert_eq!(parser.validity(&name_record).await?, Validity::Expired);
+    assert!(
+        !parser
+            .get_name_record_from_domain_tld(&expired)
+            .await?
+            .is_valid
+    );
+    assert!(matches!(
+        parser.get_owner_from_domain_tld(&expired).await,
+        Err(TldParserError::Expired {
+            expires_at: EXPIRES_AT,
+            ..
+        })
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn clock_sysvar_time_source() -> Result<(), Box<dyn Error>> {
+    let parser = fixture_parser(Some(FIXTURE_NOW))?.with_time_source(ClockSysvarTimeSource);
+    assert_eq!(parser.unix_timestamp().await?, 1_700_000_000);
+    assert!(matches!(
+        parser
+            .get_owner_from_domain_tld(&"expired.abc".parse()?)
+            .await,
+        Err(TldParserError::Expired { .. })
+    ));
+    assert_eq!(
+        parser
+            .get_owner_from_domain_tld(&"onsol.bonk".parse()?)
+            .await?,
+        OWNER
+    );
+
+    // the clock is read through the account source
+    let empty = MemoryAccountSource::new();
+    assert!(matches!(
+        ClockSysvarTimeSource.unix_timestamp(&empty).await,
+        Err(TldParserError::AccountNotFound(key)) if key == sysvar::clock::id()
+    ));
+    let invalid: MemoryAccountSource = [(
+        sysvar::clock::id(),
+        Account {
+            lamports: 1,
+            data: vec![0; 8],
+            owner: sysvar::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    )]
+    .into_iter()
+    .collect();
+    assert!(matches!(
+        ClockSysvarTimeSource.unix_timestamp(&invalid).await,
+        Err(TldParserError::Deserialization(_))
+    ));
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use tldparser::*;
+
+const EXPIRES_AT: u64 = 1_700_000_000;
+
+#[test]
+fn validity_boundaries() {
+    let grace_period = DEFAULT_GRACE_PERIOD;
+    let ends_at = EXPIRES_AT + grace_period;
+    let vectors = [
+        (0, 0, Validity::NonExpiring),
+        (0, u64::MAX, Validity::NonExpiring),
+        (EXPIRES_AT, 0, Validity::Active),
+        (EXPIRES_AT, EXPIRES_AT - 1, Validity::Active),
+        (EXPIRES_AT, EXPIRES_AT, Validity::InGracePeriod { ends_at }),
+        (EXPIRES_AT, ends_at - 1, Validity::InGracePeriod { ends_at }),
+        (EXPIRES_AT, ends_at, Validity::Expired),
+        (EXPIRES_AT, u64::MAX, Validity::Expired),
+    ];
+    for (expires_at, now, expected) in vectors {
+        assert_eq!(
+            Validity::from_expires_at(expires_at, now, grace_period),
+            expected,
+            "expires_at {} now {}",
+            expires_at,
+            now
+        );
+    }
+}
+
+#[test]
+fn grace_period_is_configurable() {
+    assert_eq!(
+        Validity::from_expires_at(EXPIRES_AT, EXPIRES_AT, 0),
+        Validity::Expired
+    );
+    assert_eq!(
+        Validity::from_expires_at(EXPIRES_AT, EXPIRES_AT, u64::MAX),
+        Validity::InGracePeriod { ends_at: u64::MAX }
+    );
+    assert!(Validity::InGracePeriod { ends_at: 1 }.is_valid());
+    assert!(Validity::NonExpiring.is_valid());
+    assert!(!Validity::Expired.is_valid());
+}